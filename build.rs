@@ -0,0 +1,374 @@
+//! Turn the embedded cooking data into static Rust tables
+//!
+//! Writes `$OUT_DIR/tables.rs`, included by `src/tables.rs`, so a `Cook`
+//! can be built without parsing any JSON or building any index at runtime.
+//! The indexes follow the rules `Cook::from_json` applies at runtime; the
+//! `tables_match_json` test checks that both agree.
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+const FILES: [&str; 6] = [
+    "src/cook_items.json",
+    "src/cook_recipes.json",
+    "src/cook_effects.json",
+    "src/cook_modifiers.json",
+    "src/names.json",
+    "src/cook_tags.json",
+];
+
+/// Actors listed under a display name shared with other actors, as in
+/// `src/locale.rs`
+const PREFER: [&str; 7] = [
+    "Item_PlantGet_B",
+    "Item_PlantGet_C",
+    "Item_PlantGet_Q",
+    "Item_PlantGet_M",
+    "Item_PlantGet_J",
+    "Item_Enemy_57",
+    "Item_Sword_080",
+];
+
+fn read(file: &str) -> Value {
+    let text = std::fs::read_to_string(file).unwrap_or_else(|e| panic!("{file}: {e}"));
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("{file}: {e}"))
+}
+
+/// Integer field `key` of `v`, which must be present
+fn int(v: &Value, key: &str) -> i64 {
+    v[key]
+        .as_i64()
+        .unwrap_or_else(|| panic!("{key}: expected an integer, got {} in {v}", v[key]))
+}
+
+/// Price field `key` of `v`, null when the item cannot be bought or sold
+fn price(v: &Value, key: &str) -> i64 {
+    match &v[key] {
+        Value::Null if v.get(key).is_some() => 0,
+        _ => int(v, key),
+    }
+}
+
+fn modifier(v: &Value, key: &str) -> String {
+    let s = v[key]
+        .as_str()
+        .unwrap_or_else(|| panic!("{key}: expected a string, got {} in {v}", v[key]));
+    match s {
+        "" => "Modifier::None".to_string(),
+        s => format!("Modifier::{s}"),
+    }
+}
+
+fn strings(v: &Value) -> Vec<&str> {
+    v.as_array()
+        .unwrap()
+        .iter()
+        .map(|x| x.as_str().unwrap())
+        .collect()
+}
+
+fn is_flat(v: &Value) -> bool {
+    v.as_array().unwrap().iter().all(|x| x.is_string())
+}
+
+/// Groups of alternatives, a flat list being a single group
+fn groups(v: &Value) -> Vec<Vec<&str>> {
+    match is_flat(v) {
+        true if v.as_array().unwrap().is_empty() => vec![],
+        true => vec![strings(v)],
+        false => v.as_array().unwrap().iter().map(strings).collect(),
+    }
+}
+
+/// Borrowed text
+fn text(s: &str) -> String {
+    format!("Cow::Borrowed({s:?})")
+}
+
+/// Generated code, with the slices it borrows as statics of their own
+///
+/// A slice of values with a destructor cannot be borrowed inline in a
+/// static, so each one is declared separately.
+#[derive(Default)]
+struct Out {
+    code: String,
+    slices: usize,
+}
+
+impl Out {
+    /// Declare a static array of `ty` holding `values`, returns its name
+    fn slice(&mut self, ty: &str, values: &[String]) -> String {
+        let name = format!("S{}", self.slices);
+        self.slices += 1;
+        writeln!(
+            self.code,
+            "static {name}: [{ty}; {}] = [{}];",
+            values.len(),
+            values.join(", ")
+        )
+        .unwrap();
+        name
+    }
+    fn texts(&mut self, v: &[&str]) -> String {
+        let v: Vec<String> = v.iter().map(|x| text(x)).collect();
+        format!("Cow::Borrowed(&{})", self.slice("Str", &v))
+    }
+    /// Recipe actors and tags are either a flat list or a list of groups
+    fn groups(&mut self, v: &Value) -> String {
+        if is_flat(v) {
+            format!("AVec::One({})", self.texts(&strings(v)))
+        } else {
+            let v = v.as_array().unwrap();
+            let g: Vec<String> = v.iter().map(|x| self.texts(&strings(x))).collect();
+            let name = self.slice("Cow<'static, [Str]>", &g);
+            format!("AVec::Two(Cow::Borrowed(&{name}))")
+        }
+    }
+    /// `Map` over `entries`, which are sorted by key
+    fn map(&mut self, ty: &str, entries: Vec<(&str, String)>) -> String {
+        let v: Vec<String> = entries
+            .into_iter()
+            .map(|(k, v)| format!("({}, {v})", text(k)))
+            .collect();
+        let name = self.slice(&format!("(Str, {ty})"), &v);
+        format!("Map::from_static(&{name})")
+    }
+    fn item(&mut self, v: &Value, id: usize, tags: &[String]) -> String {
+        let raw = strings(&v["tags"]);
+        let tag = raw.first().copied().unwrap_or("");
+        let tag_id = match tags.iter().position(|t| t == tag) {
+            Some(i) => i.to_string(),
+            None => "NO_TAG".to_string(),
+        };
+        format!(
+            "Item {{ hp: {}, effect: {}, potency: {}, time: {}, tags_raw: {}, tags: {}, \
+             name: {}, time_boost: {}, hp_boost: {}, cook_low_price: {}, key_item: {}, \
+             roast_item: {}, sell_price: {}, buy_price: {}, boost_success_rate: {}, \
+             id: {id}, tag_id: {tag_id} }}",
+            int(v, "hp"),
+            modifier(v, "effect"),
+            int(v, "potency"),
+            int(v, "time"),
+            self.texts(&raw),
+            text(tag),
+            text(v["name"].as_str().unwrap()),
+            int(v, "time_boost"),
+            int(v, "hp_boost"),
+            v["cook_low_price"].as_bool().unwrap(),
+            v["key_item"].as_bool().unwrap(),
+            v["roast_item"].as_bool().unwrap(),
+            price(v, "sell_price"),
+            price(v, "buy_price"),
+            int(v, "boost_success_rate"),
+        )
+    }
+    /// Ids of `v` in `ids`, `UNKNOWN` for those missing
+    fn group(v: &[&str], ids: &dyn Fn(&str) -> Option<usize>) -> String {
+        let v: Vec<String> = v
+            .iter()
+            .map(|x| ids(x).map_or("crate::matcher::UNKNOWN".to_string(), |i| i.to_string()))
+            .collect();
+        format!("Group::from_static(&[{}])", v.join(", "))
+    }
+    /// A recipe compiled for the matcher, as in `Matcher::new`
+    fn compiled(
+        &mut self,
+        v: &Value,
+        actor_id: &dyn Fn(&str) -> Option<usize>,
+        tag_id: &dyn Fn(&str) -> Option<usize>,
+    ) -> String {
+        let actors = groups(&v["actors"]);
+        let tags = groups(&v["tags"]);
+        // A flat list of actors is tried once per entry in the loose pass
+        let loose_actors: Vec<String> = match is_flat(&v["actors"]) {
+            true => actors
+                .iter()
+                .flat_map(|g| g.iter().map(|_| Self::group(g, actor_id)))
+                .collect(),
+            false => actors.iter().map(|g| Self::group(g, actor_id)).collect(),
+        };
+        let loose_tags: Vec<String> = tags.iter().map(|g| Self::group(g, tag_id)).collect();
+        let strict = |g: Option<&Vec<&str>>, ids| match g {
+            Some(g) => format!("Some({})", Self::group(g, ids)),
+            None => "None".to_string(),
+        };
+        format!(
+            "Compiled {{ actors: Cow::Borrowed(&{}), tags: Cow::Borrowed(&{}), \
+             strict_actors: {}, strict_tags: {} }}",
+            self.slice("Group", &loose_actors),
+            self.slice("Group", &loose_tags),
+            strict(actors.first(), actor_id),
+            strict(tags.first(), tag_id),
+        )
+    }
+}
+
+fn main() {
+    for file in FILES {
+        println!("cargo:rerun-if-changed={file}");
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+    let items = read("src/cook_items.json");
+    let recipes = read("src/cook_recipes.json");
+    let effects = read("src/cook_effects.json");
+    let modifiers = read("src/cook_modifiers.json");
+    let names = read("src/names.json");
+    let mut tags: Vec<String> = strings(&read("src/cook_tags.json"))
+        .into_iter()
+        .map(String::from)
+        .collect();
+
+    let items: BTreeMap<&String, &Value> = items.as_object().unwrap().iter().collect();
+    // Item tags missing from the tag list are added to it
+    for v in items.values() {
+        if let Some(tag) = strings(&v["tags"]).first() {
+            if !tags.iter().any(|t| t == tag) {
+                tags.push(tag.to_string());
+            }
+        }
+    }
+    let names: BTreeMap<&String, &str> = names
+        .as_object()
+        .unwrap()
+        .iter()
+        .map(|(k, v)| (k, v.as_str().unwrap()))
+        .collect();
+
+    let mut out = Out::default();
+    let mut statics = String::new();
+    let mut item_list = vec![];
+    for (id, (actor, v)) in items.iter().enumerate() {
+        item_list.push((actor.as_str(), out.item(v, id, &tags)));
+    }
+    let map = out.map("Item", item_list);
+    writeln!(statics, "/// Ingredients by actor, from `cook_items.json`").unwrap();
+    writeln!(statics, "pub static ITEMS: Map<Item> = {map};").unwrap();
+
+    let actor_id = |x: &str| items.keys().position(|a| *a == x);
+    let tag_id = |x: &str| tags.iter().position(|t| t == x);
+    let mut recipe_list = vec![];
+    let mut compiled = vec![];
+    for (id, v) in recipes.as_array().unwrap().iter().enumerate() {
+        recipe_list.push(format!(
+            "RecipeBase {{ hb: {}, name: {}, tags: {}, actors: {}, num: {}, id: {id} }}",
+            int(v, "hb"),
+            text(v["name"].as_str().unwrap()),
+            out.groups(&v["tags"]),
+            out.groups(&v["actors"]),
+            int(v, "num"),
+        ));
+        compiled.push(out.compiled(v, &actor_id, &tag_id));
+    }
+    let name = out.slice("RecipeBase", &recipe_list);
+    writeln!(
+        statics,
+        "/// Recipes in the order they are tried, from `cook_recipes.json`"
+    )
+    .unwrap();
+    writeln!(statics, "pub static RECIPES: &[RecipeBase] = &{name};").unwrap();
+    let name = out.slice("Compiled", &compiled);
+    writeln!(statics, "/// `RECIPES` compiled for matching").unwrap();
+    writeln!(
+        statics,
+        "pub(crate) static MATCHER: Matcher = Matcher::from_static(&{name});"
+    )
+    .unwrap();
+
+    let mut effect_list = vec![];
+    for v in effects.as_array().unwrap() {
+        effect_list.push(format!(
+            "Effect {{ base_time: {}, material_rate: {:?}, max: {}, min: {}, ssa: {}, \
+             kind: {}, xtype: {} }}",
+            int(v, "base_time"),
+            v["material_rate"].as_f64().unwrap() as f32,
+            int(v, "max"),
+            int(v, "min"),
+            int(v, "ssa"),
+            modifier(v, "type"),
+            text(v["xtype"].as_str().unwrap()),
+        ));
+    }
+    let name = out.slice("Effect", &effect_list);
+    writeln!(statics, "/// Cooking effects, from `cook_effects.json`").unwrap();
+    writeln!(statics, "pub static EFFECTS: &[Effect] = &{name};").unwrap();
+
+    let mut modifier_list = vec![];
+    for v in modifiers.as_array().unwrap() {
+        modifier_list.push(format!(
+            "ModifierData {{ kind: {}, elixir: {}, prefix: {} }}",
+            modifier(v, "type"),
+            text(v["elixir"].as_str().unwrap()),
+            text(v["prefix"].as_str().unwrap()),
+        ));
+    }
+    let name = out.slice("ModifierData", &modifier_list);
+    writeln!(
+        statics,
+        "/// Elixir names and dish prefixes, from `cook_modifiers.json`"
+    )
+    .unwrap();
+    writeln!(statics, "pub static MODIFIERS: &[ModifierData] = &{name};").unwrap();
+
+    let map = out.map(
+        "Str",
+        names.iter().map(|(k, v)| (k.as_str(), text(v))).collect(),
+    );
+    writeln!(
+        statics,
+        "/// English display names by actor, from `names.json`"
+    )
+    .unwrap();
+    writeln!(statics, "pub static NAMES: Map<Str> = {map};").unwrap();
+
+    // As `NameTable::new`: later actors take a shared name, except animals
+    let mut actors: BTreeMap<&str, &str> = BTreeMap::new();
+    for (actor, name) in names.iter().filter(|(k, _)| items.contains_key(*k)) {
+        if actors.contains_key(name) && actor.starts_with("Animal_") {
+            continue;
+        }
+        actors.insert(name, actor);
+    }
+    for actor in PREFER {
+        if let Some(name) = names.get(&actor.to_string()) {
+            if actors.contains_key(name) {
+                actors.insert(name, actor);
+            }
+        }
+    }
+    let map = out.map("Str", actors.iter().map(|(k, v)| (*k, text(v))).collect());
+    writeln!(
+        statics,
+        "/// Actors of the ingredients by English display name"
+    )
+    .unwrap();
+    writeln!(statics, "pub static NAME_ACTORS: Map<Str> = {map};").unwrap();
+
+    // As `dish_actors`, skipping stack labels such as "Elixir x[NUMBER]"
+    let dishes: BTreeMap<&str, &str> = names
+        .iter()
+        .filter(|(actor, name)| actor.starts_with("Item_Cook_") && !name.contains('['))
+        .map(|(actor, name)| (*name, actor.as_str()))
+        .collect();
+    let map = out.map("Str", dishes.iter().map(|(k, v)| (*k, text(v))).collect());
+    writeln!(statics, "/// `Item_Cook_*` actors by dish name").unwrap();
+    writeln!(statics, "pub static DISH_ACTORS: Map<Str> = {map};").unwrap();
+
+    let v: Vec<&str> = tags.iter().map(|x| x.as_str()).collect();
+    let list = out.texts(&v);
+    writeln!(statics, "/// Cook tags, from `cook_tags.json`").unwrap();
+    writeln!(
+        statics,
+        "pub static TAGS: &[Str] = &{};",
+        &list["Cow::Borrowed(&".len()..list.len() - 1]
+    )
+    .unwrap();
+
+    let mut code = String::new();
+    writeln!(code, "// Generated by build.rs, do not edit").unwrap();
+    code.push_str(&out.code);
+    code.push_str(&statics);
+    let dest = Path::new(&std::env::var("OUT_DIR").unwrap()).join("tables.rs");
+    std::fs::write(dest, code).unwrap();
+}
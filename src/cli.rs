@@ -0,0 +1,301 @@
+use cooking::{Cook, CookContext, CookError, Explanation, Msbt, Recipe, WMC};
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: cooking [--json] <command> [args...]
+
+Commands:
+    cook <items...>     Cook up to 5 ingredients by name or actor, e.g.
+                        cooking cook Apple \"Hearty Radish\" Item_Plant_B
+    explain <items...>  Show every recipe tried and why it did or did not match
+    items               List known ingredients
+    msbt <files...>     Print the display names by actor in ActorType
+                        message files, in the format of --names
+    recipes             List known recipes
+    wmc <price> <hp>    Show the weapon modifiers for a price and hp value
+
+Options:
+    --json              Print output as JSON
+    --blood-moon        Cook during a Blood Moon, always a critical success
+    --names=<lang>:<file>
+                        Read and print names in <lang>, loaded from <file>,
+                        either a JSON object of display names by actor or
+                        an ActorType .msbt message file; may be repeated.
+                        Dishes with an effect keep their English name
+    -v, --verbose       Log how the result is computed to stderr
+    -h, --help          Print this help";
+
+/// Writes log records, with their key-values, to stderr
+struct StderrLogger;
+
+struct KeyValues(String);
+
+impl<'kvs> log::kv::VisitSource<'kvs> for KeyValues {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push_str(&format!(" {key}={value}"));
+        Ok(())
+    }
+}
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        let mut kv = KeyValues(String::new());
+        let _ = record.key_values().visit(&mut kv);
+        eprintln!("{:<5} {}{}", record.level(), record.args(), kv.0);
+    }
+    fn flush(&self) {}
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), CookError> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn print_wmc(w: &WMC) {
+    println!("Weapon Modifiers (price {}, hp {})", w.price, w.hp);
+    if let Some(v) = w.attack_up {
+        println!("    Attack Up: +{v}");
+    }
+    if w.durability_up {
+        println!("    Durability Up");
+    }
+    if w.critical_hit {
+        println!("    Critical Hit");
+    }
+    if let Some(v) = w.long_throw {
+        println!("    Long Throw: {v}");
+    }
+    if let Some(v) = w.multi_shot {
+        println!("    Multi-Shot: {v}");
+    }
+    if w.zoom {
+        println!("    Zoom");
+    }
+    if let Some(v) = w.quick_shot {
+        println!("    Quick Shot: {v}");
+    }
+    if let Some(v) = w.surf_master {
+        println!("    Surf Master: {v}");
+    }
+    if let Some(v) = w.shield_guard_up {
+        println!("    Shield Guard Up: +{v}");
+    }
+    if w.yellow_modifier {
+        println!("    Yellow Modifier");
+    }
+}
+
+fn print_recipe(cook: &Cook, r: &Recipe) {
+    let name = match cook.dish_display_name(r) {
+        Ok(name) => name,
+        Err(_) => format!("{} (no {} name)", r.display_name(), cook.language()),
+    };
+    let items = cook.localized_names(&r.ingredients);
+    println!("{name} (id {})", r.id);
+    println!("  Actor:       {}", r.actor);
+    println!("  Ingredients: {}", items.join(", "));
+    println!("  Description: {}", r.description());
+    println!("  Hearts:      {} (hp {})", r.hp, r.hp.quarters());
+    if r.effect != cooking::Modifier::None {
+        println!(
+            "  Effect:      {} level {} ({}, potency {})",
+            r.effect, r.level, r.effect_level_name, r.potency
+        );
+    }
+    if r.effects_cancelled {
+        let effects: Vec<String> = r
+            .effects
+            .iter()
+            .map(|e| {
+                let items: Vec<&str> = e.ingredients.iter().map(|i| items[*i].as_str()).collect();
+                format!("{} ({})", e.effect, items.join(", "))
+            })
+            .collect();
+        println!("  Cancelled:   {}", effects.join(", "));
+    }
+    if !r.time.is_zero() {
+        println!("  Duration:    {}", r.time);
+    }
+    if r.stamina > 0.0 {
+        println!("  Stamina:     {} wheels", r.stamina);
+    }
+    if r.stamina_extra > 0.0 {
+        println!("  Extra:       {} wheels", r.stamina_extra);
+    }
+    println!("  Price:       {} rupees", r.price);
+    println!("  Crit Rate:   {}%", r.crit_rate);
+    if r.monster_rng {
+        println!("  Monster Extract:");
+        for x in &r.extract_outcomes {
+            let time = x.roll.time.map(|t| format!(", {t}")).unwrap_or_default();
+            println!(
+                "    {:>5.1}%  {}{}",
+                x.probability * 100.0,
+                x.recipe.hp,
+                time
+            );
+        }
+    }
+    print_wmc(&r.wmc);
+}
+
+fn print_explanation(cook: &Cook, e: &Explanation) {
+    let items = cook.localized_names(&e.actors);
+    println!("Ingredients: {}", items.join(", "));
+    for a in &e.attempts {
+        match &a.failure {
+            None => println!("{:>3} {:<28} {:?} matched", a.id, a.name, a.pass),
+            Some(f) => println!("{:>3} {:<28} {:?} {:?}", a.id, a.name, a.pass, f),
+        }
+        for c in &a.consumed {
+            let used: Vec<&str> = c.ingredients.iter().map(|i| items[*i].as_str()).collect();
+            println!(
+                "        {:?} {} uses {}",
+                c.requirement,
+                c.matched,
+                used.join(", ")
+            );
+        }
+    }
+    if e.fallback {
+        println!("No recipe matched, using {}", e.name);
+    }
+}
+
+fn parse_i32(arg: Option<&String>, what: &str) -> Result<i32, String> {
+    let arg = arg.ok_or_else(|| format!("missing {what}"))?;
+    arg.parse().map_err(|_| format!("invalid {what}: {arg}"))
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let json = args.iter().any(|a| a == "--json");
+    let verbose = args.iter().any(|a| a == "-v" || a == "--verbose");
+    let ctx = CookContext {
+        blood_moon: args.iter().any(|a| a == "--blood-moon"),
+        ..CookContext::default()
+    };
+    let names: Vec<&str> = args
+        .iter()
+        .filter_map(|a| a.strip_prefix("--names="))
+        .collect();
+    let args: Vec<&String> = args
+        .iter()
+        .filter(|a| !["--json", "-v", "--verbose", "--blood-moon"].contains(&a.as_str()))
+        .filter(|a| !a.starts_with("--names="))
+        .collect();
+    if verbose {
+        static LOGGER: StderrLogger = StderrLogger;
+        log::set_logger(&LOGGER).map_err(|e| e.to_string())?;
+        log::set_max_level(log::LevelFilter::Trace);
+    }
+    let Some((cmd, rest)) = args.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    let mut cook = Cook::try_new().map_err(|e| e.to_string())?;
+    for names in names {
+        let (lang, file) = names
+            .split_once(':')
+            .ok_or_else(|| format!("invalid --names={names}, expected <lang>:<file>"))?;
+        let fp = std::fs::File::open(file).map_err(|e| format!("{file}: {e}"))?;
+        let fp = std::io::BufReader::new(fp);
+        if file.ends_with(".msbt") {
+            cook.load_msbt(lang, fp)
+        } else {
+            cook.load_names(lang, fp)
+        }
+        .map_err(|e| format!("{file}: {e}"))?;
+        cook.set_language(lang).map_err(|e| e.to_string())?;
+    }
+    match cmd.as_str() {
+        "cook" => {
+            let r = cook.try_cook_with(rest, &ctx).map_err(|e| e.to_string())?;
+            if json {
+                print_json(&r).map_err(|e| e.to_string())?;
+            } else {
+                print_recipe(&cook, &r);
+            }
+        }
+        "explain" => {
+            let e = cook.explain(rest).map_err(|e| e.to_string())?;
+            if json {
+                print_json(&e).map_err(|e| e.to_string())?;
+            } else {
+                print_explanation(&cook, &e);
+            }
+        }
+        "items" => {
+            let mut items: Vec<(&str, &str)> = cook
+                .data
+                .keys()
+                .filter_map(|actor| cook.names.get(actor).map(|name| (name.as_ref(), actor)))
+                .collect();
+            items.sort();
+            if json {
+                let items: Vec<_> = items
+                    .iter()
+                    .map(|(name, actor)| serde_json::json!({"name": name, "actor": actor}))
+                    .collect();
+                print_json(&items).map_err(|e| e.to_string())?;
+            } else {
+                for (name, actor) in items {
+                    println!("{name:<32} {actor}");
+                }
+            }
+        }
+        "msbt" => {
+            let mut names = serde_json::Map::new();
+            for file in rest {
+                let fp = std::fs::File::open(file).map_err(|e| format!("{file}: {e}"))?;
+                let m =
+                    Msbt::read(std::io::BufReader::new(fp)).map_err(|e| format!("{file}: {e}"))?;
+                names.extend(m.names().into_iter().map(|(k, v)| (k, v.into())));
+            }
+            names.sort_keys();
+            print_json(&names).map_err(|e| e.to_string())?;
+        }
+        "recipes" => {
+            if json {
+                let recipes: Vec<_> = cook
+                    .recipes
+                    .iter()
+                    .map(|r| serde_json::json!({"id": r.id(), "name": r.name()}))
+                    .collect();
+                print_json(&recipes).map_err(|e| e.to_string())?;
+            } else {
+                for r in cook.recipes.iter() {
+                    println!("{:>3} {}", r.id(), r.name());
+                }
+            }
+        }
+        "wmc" => {
+            let price = parse_i32(rest.first().copied(), "price")?;
+            let hp = parse_i32(rest.get(1).copied(), "hp")?;
+            let w = WMC::new(price, hp);
+            if json {
+                print_json(&w).map_err(|e| e.to_string())?;
+            } else {
+                print_wmc(&w);
+            }
+        }
+        "-h" | "--help" | "help" => println!("{USAGE}"),
+        _ => return Err(format!("unknown command: {cmd}\n\n{USAGE}")),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{msg}");
+            ExitCode::FAILURE
+        }
+    }
+}
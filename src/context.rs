@@ -0,0 +1,38 @@
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Circumstances of a cook that change its outcome
+///
+/// The default is cooking in a pot outside of a Blood Moon, which is what
+/// [`crate::Cook::try_cook`] assumes.
+///
+/// Only the cooking pot is modelled.  Roasting over a fire, freezing and
+/// boiling in a hot spring turn one ingredient into another without a crit
+/// roll, and the crate has no table of what they produce.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct CookContext {
+    /// Cooking during a Blood Moon is always a critical success
+    #[cfg_attr(feature = "json", serde(default))]
+    pub blood_moon: bool,
+    /// Crit rate in percent to use instead of the one from the ingredients
+    #[cfg_attr(feature = "json", serde(default))]
+    pub crit_rate: Option<i32>,
+}
+
+impl CookContext {
+    /// Cooking while the Blood Moon rises
+    pub fn blood_moon() -> Self {
+        Self {
+            blood_moon: true,
+            ..Self::default()
+        }
+    }
+    /// Crit rate of a cook with a crit rate of `rate` from its ingredients
+    pub(crate) fn crit_rate(&self, rate: i32) -> i32 {
+        if self.blood_moon {
+            return 100;
+        }
+        self.crit_rate.unwrap_or(rate).clamp(0, 100)
+    }
+}
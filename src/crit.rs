@@ -0,0 +1,163 @@
+//! Critical success outcomes
+//!
+//! A critical success adds a single bonus to the dish.  Which bonuses are
+//! possible depends on the effect:
+//!
+//! - No effect: hearts (`ssa` of LifeRecover, +3 hearts), unless already
+//!   at full recovery
+//! - LifeMaxUp, GutsRecover, ExGutsMaxUp: potency (`ssa` of the effect),
+//!   unless already at the effect maximum
+//! - Other effects: one of hearts, potency or duration (+05:00), picked
+//!   uniformly among those that would change the dish; hearts are skipped
+//!   when already at full recovery and potency when at the effect maximum
+//!
+//! Potency is never picked for an effect missing from `cook_effects.json`.
+//!
+//! The amounts come from the `ssa` values in `cook_effects.json`.  The
+//! choice between bonuses is an assumption: it has not been checked against
+//! the game's code or against recorded critical successes.
+use crate::{Cook, Duration, Effect, Hearts, Modifier, Potency, Recipe};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Bonus added by a critical success
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum CritBonus {
+    Hearts,
+    Potency,
+    Duration,
+}
+
+/// One possible critical success
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct CritOutcome {
+    pub bonus: CritBonus,
+    /// Chance of this outcome when cooking, crit rate included, 0 to 1
+    pub probability: f32,
+    /// Dish with the bonus applied
+    pub recipe: Recipe,
+}
+
+/// Duration added by a critical success
+pub(crate) const CRIT_TIME: Duration = Duration::from_mins(5);
+
+impl Cook {
+    /// Bonuses a critical success can add to `r`, in the order they are picked
+    pub fn crit_bonuses(&self, r: &Recipe) -> Vec<CritBonus> {
+        if r.crit_rate <= 0 {
+            return vec![];
+        }
+        let potency = self.try_get_effect(r.effect).is_ok_and(|e| !at_max(r, e));
+        match r.effect {
+            Modifier::None | Modifier::LifeRecover if r.hp.is_full() => vec![],
+            Modifier::None | Modifier::LifeRecover => vec![CritBonus::Hearts],
+            Modifier::LifeMaxUp | Modifier::GutsRecover | Modifier::ExGutsMaxUp => match potency {
+                true => vec![CritBonus::Potency],
+                false => vec![],
+            },
+            _ => {
+                let mut out = vec![];
+                if !r.hp.is_full() {
+                    out.push(CritBonus::Hearts);
+                }
+                if potency {
+                    out.push(CritBonus::Potency);
+                }
+                out.push(CritBonus::Duration);
+                out
+            }
+        }
+    }
+    /// Every critical success outcome for `r` with its probability
+    pub fn crit_outcomes(&self, r: &Recipe) -> Vec<CritOutcome> {
+        let bonuses = self.crit_bonuses(r);
+        let n = bonuses.len() as f32;
+        bonuses
+            .into_iter()
+            .map(|bonus| CritOutcome {
+                bonus,
+                probability: r.crit_rate as f32 / 100.0 / n,
+                recipe: self.apply_crit(r, bonus),
+            })
+            .collect()
+    }
+    /// Fill in the crit outcomes of `r` and the `*_crit` summary values
+    ///
+    /// The summary values are those of the outcome adding that bonus, or the
+    /// current value if the bonus cannot happen.
+    pub(crate) fn set_crit(&self, r: &mut Recipe) {
+        r.crit_outcomes = self.crit_outcomes(r);
+        let crit = |b| {
+            r.crit_outcomes
+                .iter()
+                .find(|c| c.bonus == b)
+                .map(|c| &c.recipe)
+        };
+        let hearts = crit(CritBonus::Hearts);
+        let time = crit(CritBonus::Duration);
+        let potency = crit(CritBonus::Potency);
+        r.hp_crit = hearts.map_or(r.hp, |x| x.hp);
+        r.time_crit = time.map_or(r.time, |x| x.time);
+        r.level_crit = potency.map_or(r.level, |x| x.level);
+        r.stamina_crit = potency.map_or(r.stamina, |x| x.stamina);
+        r.stamina_extra_crit = potency.map_or(r.stamina_extra, |x| x.stamina_extra);
+    }
+    /// `r` with the critical success `bonus` added
+    pub fn apply_crit(&self, r: &Recipe, bonus: CritBonus) -> Recipe {
+        let mut out = r.clone();
+        out.crit_outcomes.clear();
+        out.extract_outcomes.clear();
+        let ssa = |m: Modifier| self.try_get_effect(m).map(|e| e.ssa).unwrap_or(0);
+        match bonus {
+            CritBonus::Hearts => {
+                let bonus = Hearts::from_quarters(ssa(Modifier::LifeRecover));
+                out.set_hp((out.hp + bonus).capped());
+            }
+            CritBonus::Duration => out.time = (out.time + CRIT_TIME).capped(),
+            CritBonus::Potency => {
+                let Ok(e) = self.try_get_effect(out.effect) else {
+                    return out;
+                };
+                match out.effect {
+                    // Level counts whole yellow hearts, ssa is in quarters
+                    Modifier::LifeMaxUp => out.level = (out.level + e.ssa / 4).min(e.max / 4),
+                    Modifier::GutsRecover => out.stamina = add_wheels(out.stamina, e, e.max),
+                    Modifier::ExGutsMaxUp => {
+                        out.stamina_extra = add_wheels(out.stamina_extra, e, e.max)
+                    }
+                    _ => {
+                        out.level = (out.level + e.ssa).min(e.max);
+                        out.effect_level_name = Potency::from_level(out.level);
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The potency of `r`, with effect record `e`, cannot go any higher
+fn at_max(r: &Recipe, e: &Effect) -> bool {
+    let fifths = |v: f32| (v * 5.0).round() as i32;
+    match r.effect {
+        // Level counts whole yellow hearts, `max` is in quarters
+        Modifier::LifeMaxUp => r.level >= e.max / 4,
+        Modifier::GutsRecover => fifths(r.stamina) >= e.max,
+        Modifier::ExGutsMaxUp => fifths(r.stamina_extra) >= e.max,
+        _ => r.level >= e.max,
+    }
+}
+
+/// Stamina wheels for `fifths` of a wheel
+pub(crate) fn wheels(fifths: i32) -> f32 {
+    fifths as f32 / 5.0
+}
+
+/// Stamina `v` in wheels plus the effect's `ssa`, counted in fifths of a
+/// wheel and capped at `max` fifths
+fn add_wheels(v: f32, e: &Effect, max: i32) -> f32 {
+    let fifths = (v * 5.0).round() as i32;
+    wheels((fifths + e.ssa).min(max))
+}
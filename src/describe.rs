@@ -0,0 +1,122 @@
+//! Meal description text, as shown for a dish in the pouch
+//!
+//! Composed of up to three parts: hearts restored, the effect, and the
+//! effect duration, e.g. "Restores 3 hearts. Grants a low-level attack
+//! boost. 01:20".
+//!
+//! The wording has not been checked against text recorded from the game;
+//! the tests only pin the format described here.
+use crate::{Modifier, Recipe};
+
+/// What a boosting or resisting effect grants
+fn grants(effect: Modifier) -> Option<&'static str> {
+    let s = match effect {
+        Modifier::AttackUp => "attack boost",
+        Modifier::DefenseUp => "defense boost",
+        Modifier::ResistCold => "cold resistance",
+        Modifier::ResistHot => "heat resistance",
+        Modifier::ResistElectric => "shock resistance",
+        Modifier::Fireproof => "flame guard",
+        Modifier::MovingSpeed => "movement speed boost",
+        Modifier::Quietness => "stealth boost",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn level_name(level: i32) -> &'static str {
+    match level {
+        ..=1 => "low-level",
+        2 => "mid-level",
+        _ => "high-level",
+    }
+}
+
+/// Stamina wheels with `adjective` before the unit, e.g. "1⅖ stamina wheels"
+fn wheels(stamina: f32, adjective: &str) -> String {
+    let fifths = (stamina * 5.0).round() as i32;
+    let frac = ["", "⅕", "⅖", "⅗", "⅘"][fifths.rem_euclid(5) as usize];
+    let unit = if fifths > 0 && fifths <= 5 {
+        "wheel"
+    } else {
+        "wheels"
+    };
+    match (fifths / 5, frac) {
+        (0, "") => format!("0 {adjective}stamina {unit}"),
+        (0, _) => format!("{frac} {adjective}stamina {unit}"),
+        (n, _) => format!("{n}{frac} {adjective}stamina {unit}"),
+    }
+}
+
+impl Recipe {
+    /// Description shown for the dish in game
+    pub fn description(&self) -> String {
+        let mut parts = vec![];
+        if self.effect == Modifier::LifeMaxUp {
+            let unit = if self.level == 1 { "heart" } else { "hearts" };
+            parts.push(format!(
+                "Fully restores hearts and adds {} extra {unit}.",
+                self.level
+            ));
+        } else if self.hp.is_full() {
+            parts.push("Fully restores hearts.".to_string());
+        } else if !self.hp.is_zero() {
+            parts.push(format!("Restores {}.", self.hp));
+        }
+        match self.effect {
+            Modifier::GutsRecover => parts.push(format!("Restores {}.", wheels(self.stamina, ""))),
+            Modifier::ExGutsMaxUp => parts.push(format!(
+                "Fully restores stamina and adds {}.",
+                wheels(self.stamina_extra, "extra ")
+            )),
+            effect => {
+                if let Some(what) = grants(effect) {
+                    parts.push(format!("Grants a {} {what}.", level_name(self.level)));
+                    if !self.time.is_zero() {
+                        parts.push(self.time.to_string());
+                    }
+                }
+            }
+        }
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cook;
+
+    #[test]
+    fn description() {
+        let c = Cook::new();
+        let d = |items: &[&str]| c.cook(items).description();
+        assert_eq!(
+            d(&["Mighty Bananas", "Raw Meat"]),
+            "Restores 3 hearts. Grants a low-level attack boost. 01:20"
+        );
+        assert_eq!(
+            d(&["Hearty Radish"]),
+            "Fully restores hearts and adds 3 extra hearts."
+        );
+        assert_eq!(
+            d(&["Stamella Shroom"; 5]),
+            "Restores 5 hearts. Restores 1⅖ stamina wheels."
+        );
+        assert_eq!(
+            d(&["Endura Carrot"; 5]),
+            "Restores 20 hearts. Fully restores stamina and adds 2 extra stamina wheels."
+        );
+        assert_eq!(d(&["Wood"]), "Restores ¼ heart.");
+        assert_eq!(d(&["Apple"]), "Restores 1 heart.");
+        // Less than a whole wheel
+        let r = Recipe {
+            effect: Modifier::GutsRecover,
+            stamina: 0.4,
+            ..Default::default()
+        };
+        assert_eq!(r.description(), "Restores ⅖ stamina wheel.");
+        assert_eq!(wheels(0.0, "extra "), "0 extra stamina wheels");
+        assert_eq!(wheels(1.0, ""), "1 stamina wheel");
+    }
+}
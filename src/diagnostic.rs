@@ -0,0 +1,29 @@
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Non-fatal problem found in the cooking data while building a [`crate::Cook`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Diagnostic {
+    /// Item has more than one cook tag, only the first one is used
+    MultipleCookTags { actor: String, tags: Vec<String> },
+    /// Item has no display name and can only be given by its actor name
+    MissingName { actor: String },
+    /// Recipe has no `Item_Cook_*` actor with the same name
+    MissingDishActor { recipe: String },
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Diagnostic::MultipleCookTags { actor, tags } => {
+                write!(f, "Item {actor} has > 1 cook tag {tags:?}, using the first")
+            }
+            Diagnostic::MissingName { actor } => write!(f, "Missing name for {actor}"),
+            Diagnostic::MissingDishActor { recipe } => {
+                write!(f, "No Item_Cook actor for recipe {recipe}")
+            }
+        }
+    }
+}
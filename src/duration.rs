@@ -0,0 +1,132 @@
+use crate::CookError;
+#[cfg(feature = "json")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::Add;
+use std::str::FromStr;
+
+/// Length of an effect, counted in seconds
+///
+/// Displayed and parsed as `mm:ss`.  Serialized as seconds; deserialized
+/// from either seconds or a `"mm:ss"` string.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(i32);
+
+impl Duration {
+    pub const ZERO: Duration = Duration(0);
+    /// Longest duration a dish can have, 30:00
+    pub const MAX: Duration = Duration(30 * 60);
+
+    pub const fn from_secs(secs: i32) -> Self {
+        Duration(secs)
+    }
+    pub const fn from_mins(mins: i32) -> Self {
+        Duration(mins * 60)
+    }
+    pub const fn secs(self) -> i32 {
+        self.0
+    }
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+    /// This duration, at most [`Duration::MAX`]
+    pub fn capped(self) -> Self {
+        self.min(Self::MAX)
+    }
+}
+
+impl Add for Duration {
+    type Output = Duration;
+    fn add(self, other: Duration) -> Duration {
+        Duration(self.0 + other.0)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+impl FromStr for Duration {
+    type Err = CookError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CookError::InvalidDuration(s.to_string());
+        let (m, sec) = s.trim().split_once(':').ok_or_else(invalid)?;
+        let m: i32 = m.parse().map_err(|_| invalid())?;
+        let sec: i32 = sec.parse().map_err(|_| invalid())?;
+        if m < 0 || !(0..60).contains(&sec) {
+            return Err(invalid());
+        }
+        Ok(Duration(m * 60 + sec))
+    }
+}
+
+#[cfg(feature = "json")]
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i32(self.0)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Form {
+            Secs(i32),
+            Text(String),
+        }
+        match Form::deserialize(deserializer)? {
+            Form::Secs(v) => Ok(Duration(v)),
+            Form::Text(v) => v.parse().map_err(de::Error::custom),
+        }
+    }
+}
+
+/// Serialize a [`Duration`] as `"mm:ss"`, for `#[serde(with = "...")]`
+#[cfg(feature = "json")]
+pub mod mmss {
+    use super::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(v: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(v)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        Duration::deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_and_parse() {
+        assert_eq!(Duration::from_secs(490).to_string(), "08:10");
+        assert_eq!(Duration::MAX.to_string(), "30:00");
+        assert_eq!(
+            "08:10".parse::<Duration>().unwrap(),
+            Duration::from_secs(490)
+        );
+        assert_eq!("0:05".parse::<Duration>().unwrap(), Duration::from_secs(5));
+        assert!("8:60".parse::<Duration>().is_err());
+        assert!("490".parse::<Duration>().is_err());
+        assert_eq!(
+            (Duration::from_mins(29) + Duration::from_mins(5)).capped(),
+            Duration::MAX
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn serde_forms() {
+        let a: Duration = serde_json::from_str("\"02:00\"").unwrap();
+        let b: Duration = serde_json::from_str("120").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(serde_json::to_string(&a).unwrap(), "120");
+        assert!(serde_json::from_str::<Duration>("\"2m\"").is_err());
+    }
+}
@@ -0,0 +1,208 @@
+//! Why a recipe was chosen
+//!
+//! Recipes are tried in two passes: first the single ingredient recipes,
+//! which must use up every ingredient (strict), then the remaining ones,
+//! which only need their actors and tags present (loose).  The first recipe
+//! to match is cooked, Dubious Food if none does.
+use crate::{RecipeBase, Str};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Pass over the recipe table a recipe was tried in
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Pass {
+    /// Only a single kind of ingredient, all of which must be used
+    Strict,
+    /// Required actors and tags must be present, others are ignored
+    Loose,
+}
+
+/// What a recipe asked for
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Requirement {
+    Actor,
+    Tag,
+}
+
+/// Ingredients used up by one actor or tag of a recipe
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Consumed {
+    pub requirement: Requirement,
+    /// Accepted actors or tags, in the order they are tried
+    pub options: Vec<String>,
+    /// Actor or tag found
+    pub matched: String,
+    /// Positions of the ingredients used up
+    pub ingredients: Vec<usize>,
+}
+
+/// Reason a recipe did not match
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum Failure {
+    /// Strict recipes need exactly one kind of ingredient
+    NotSingleIngredient { unique: usize },
+    /// None of the actors are among the ingredients left
+    MissingActor { options: Vec<String> },
+    /// None of the tags are among the ingredients left
+    MissingTag { options: Vec<String> },
+    /// Strict recipes must use up every ingredient
+    LeftOver { ingredients: Vec<usize> },
+}
+
+/// One recipe tried against the ingredients
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Attempt {
+    pub id: i32,
+    pub name: String,
+    pub pass: Pass,
+    pub consumed: Vec<Consumed>,
+    /// `None` if the recipe matched
+    pub failure: Option<Failure>,
+}
+
+impl Attempt {
+    pub fn matched(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Every recipe tried, in order, up to and including the one chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Explanation {
+    /// Ingredients by display name
+    pub items: Vec<String>,
+    /// Ingredients by actor name
+    pub actors: Vec<String>,
+    pub attempts: Vec<Attempt>,
+    /// Recipe chosen
+    pub id: i32,
+    pub name: String,
+    /// No recipe matched and Dubious Food was used
+    pub fallback: bool,
+}
+
+impl RecipeBase {
+    /// Try this recipe against the ingredients `items` with cook `tags`
+    pub(crate) fn attempt(&self, items: &[String], tags: &[String], strict: bool) -> Attempt {
+        let mut out = Attempt {
+            id: self.id,
+            name: self.name.to_string(),
+            pass: if strict { Pass::Strict } else { Pass::Loose },
+            consumed: vec![],
+            failure: None,
+        };
+        out.failure = if strict {
+            self.attempt_strict(items, tags, &mut out.consumed)
+        } else {
+            self.attempt_loose(items, tags, &mut out.consumed)
+        };
+        out
+    }
+    fn attempt_strict(
+        &self,
+        items: &[String],
+        tags: &[String],
+        consumed: &mut Vec<Consumed>,
+    ) -> Option<Failure> {
+        let mut unique = items.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+        if unique.len() != 1 {
+            return Some(Failure::NotSingleIngredient {
+                unique: unique.len(),
+            });
+        }
+        let mut left: Vec<usize> = (0..items.len()).collect();
+        if self.actors.len() > 0 {
+            let options = self.actors.id(0);
+            let c = take(Requirement::Actor, options, items, items, &mut left);
+            let Some(c) = c else {
+                return Some(Failure::MissingActor {
+                    options: strings(options),
+                });
+            };
+            consumed.push(c);
+        }
+        if self.tags.len() > 0 {
+            let options = self.tags.id(0);
+            let c = take(Requirement::Tag, options, tags, tags, &mut left);
+            let Some(c) = c else {
+                return Some(Failure::MissingTag {
+                    options: strings(options),
+                });
+            };
+            consumed.push(c);
+        }
+        if !left.is_empty() {
+            return Some(Failure::LeftOver { ingredients: left });
+        }
+        None
+    }
+    fn attempt_loose(
+        &self,
+        items: &[String],
+        tags: &[String],
+        consumed: &mut Vec<Consumed>,
+    ) -> Option<Failure> {
+        let mut left: Vec<usize> = (0..items.len()).collect();
+        for i in 0..self.actors.len() {
+            let options = self.actors.id(i);
+            let c = take(Requirement::Actor, options, items, items, &mut left);
+            let Some(c) = c else {
+                return Some(Failure::MissingActor {
+                    options: strings(options),
+                });
+            };
+            consumed.push(c);
+        }
+        for options in self.tags.groups() {
+            // The tag picks an ingredient, every copy of which is used up
+            let c = take(Requirement::Tag, options, tags, items, &mut left);
+            let Some(c) = c else {
+                return Some(Failure::MissingTag {
+                    options: strings(options),
+                });
+            };
+            consumed.push(c);
+        }
+        None
+    }
+}
+
+fn strings(v: &[Str]) -> Vec<String> {
+    v.iter().map(|x| x.to_string()).collect()
+}
+
+/// Use up ingredients for the first of `options` found in `keys`
+///
+/// Every ingredient left sharing the `remove` value of the first one found
+/// is removed from `left`.
+fn take(
+    requirement: Requirement,
+    options: &[Str],
+    keys: &[String],
+    remove: &[String],
+    left: &mut Vec<usize>,
+) -> Option<Consumed> {
+    let (matched, k) = options
+        .iter()
+        .find_map(|opt| left.iter().find(|&&k| keys[k] == *opt).map(|k| (opt, *k)))?;
+    let ingredients: Vec<usize> = left
+        .iter()
+        .copied()
+        .filter(|&i| remove[i] == remove[k])
+        .collect();
+    left.retain(|i| !ingredients.contains(i));
+    Some(Consumed {
+        requirement,
+        options: strings(options),
+        matched: matched.to_string(),
+        ingredients,
+    })
+}
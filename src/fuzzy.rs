@@ -0,0 +1,88 @@
+/// Fold a name for loose comparison
+///
+/// Lowercases, strips accents ("Sautéed" -> "sauteed"), drops punctuation
+/// and collapses whitespace, so "hearty  RADISH" and "Hearty Radish"
+/// compare equal.
+pub fn normalize(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars().flat_map(char::to_lowercase) {
+        let folded = match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => "a",
+            'ç' => "c",
+            'è' | 'é' | 'ê' | 'ë' => "e",
+            'ì' | 'í' | 'î' | 'ï' => "i",
+            'ñ' => "n",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => "o",
+            'ù' | 'ú' | 'û' | 'ü' => "u",
+            'ý' | 'ÿ' => "y",
+            'æ' => "ae",
+            'œ' => "oe",
+            'ß' => "ss",
+            '-' | '_' => " ",
+            c if c.is_alphanumeric() || c.is_whitespace() => {
+                out.push(c);
+                continue;
+            }
+            _ => continue,
+        };
+        out.push_str(folded);
+    }
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Levenshtein edit distance between two strings, by character
+pub fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = if ca == *cb { 0 } else { 1 };
+            cur[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Names closest to `name`, best first, at most `n` of them
+///
+/// Only names within a few edits (scaled by length) are considered.
+pub fn suggestions<'a, I>(name: &str, names: I, n: usize) -> Vec<String>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let key = normalize(name);
+    let limit = (key.chars().count() / 3).max(2);
+    let mut found: Vec<(usize, &str)> = names
+        .into_iter()
+        .map(|x| (distance(&key, &normalize(x)), x))
+        .filter(|(d, _)| *d <= limit)
+        .collect();
+    found.sort();
+    found
+        .into_iter()
+        .take(n)
+        .map(|(_, x)| x.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn normalize_test() {
+        assert_eq!(normalize("Sautéed Peppers"), "sauteed peppers");
+        assert_eq!(normalize("  hearty   RADISH "), "hearty radish");
+        assert_eq!(normalize("Naydra's Scale"), "naydras scale");
+        assert_eq!(normalize("Rock-Hard Food"), "rock hard food");
+    }
+    #[test]
+    fn distance_test() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("carot", "carrot"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("abc", ""), 3);
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(feature = "json")]
+use serde::{de, Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::ops::Add;
+
+/// Health restored by a dish, counted in quarter hearts
+///
+/// Serialized as the number of quarters; deserialized from a whole number,
+/// written either as an integer or a float (`16.0`).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize))]
+#[cfg_attr(feature = "json", serde(transparent))]
+pub struct Hearts(i32);
+
+impl Hearts {
+    pub const ZERO: Hearts = Hearts(0);
+    /// Most a dish can restore, 30 hearts
+    pub const MAX: Hearts = Hearts(30 * 4);
+
+    pub const fn from_quarters(quarters: i32) -> Self {
+        Hearts(quarters)
+    }
+    pub const fn from_hearts(hearts: i32) -> Self {
+        Hearts(hearts * 4)
+    }
+    pub const fn quarters(self) -> i32 {
+        self.0
+    }
+    /// Whole and fractional hearts, e.g. 3.25
+    pub fn hearts(self) -> f32 {
+        self.0 as f32 / 4.0
+    }
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+    /// Restores every heart
+    pub fn is_full(self) -> bool {
+        self >= Self::MAX
+    }
+    /// These hearts, at most [`Hearts::MAX`]
+    pub fn capped(self) -> Self {
+        self.min(Self::MAX)
+    }
+}
+
+impl Add for Hearts {
+    type Output = Hearts;
+    fn add(self, other: Hearts) -> Hearts {
+        Hearts(self.0 + other.0)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de> Deserialize<'de> for Hearts {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = f64::deserialize(deserializer)?;
+        if v.fract() != 0.0 || v.abs() > i32::MAX as f64 {
+            return Err(de::Error::custom(format!(
+                "expected quarter hearts, got {v}"
+            )));
+        }
+        Ok(Hearts(v as i32))
+    }
+}
+
+impl fmt::Display for Hearts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_full() {
+            return write!(f, "Full recovery");
+        }
+        let whole = self.0 / 4;
+        let frac = ["", "¼", "½", "¾"][self.0.rem_euclid(4) as usize];
+        let unit = if self.0 > 0 && self.0 <= 4 {
+            "heart"
+        } else {
+            "hearts"
+        };
+        match (whole, frac) {
+            (0, "") => write!(f, "0 {unit}"),
+            (0, _) => write!(f, "{frac} {unit}"),
+            _ => write!(f, "{whole}{frac} {unit}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let s = |q| Hearts::from_quarters(q).to_string();
+        assert_eq!(s(0), "0 hearts");
+        assert_eq!(s(1), "¼ heart");
+        assert_eq!(s(4), "1 heart");
+        assert_eq!(s(13), "3¼ hearts");
+        assert_eq!(s(22), "5½ hearts");
+        assert_eq!(s(119), "29¾ hearts");
+        assert_eq!(s(120), "Full recovery");
+        assert_eq!(Hearts::from_quarters(130).capped(), Hearts::MAX);
+        assert_eq!(Hearts::from_quarters(13).hearts(), 3.25);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn serde() {
+        let a: Hearts = serde_json::from_str("16").unwrap();
+        let b: Hearts = serde_json::from_str("16.0").unwrap();
+        assert_eq!(a, b);
+        assert_eq!(serde_json::to_string(&a).unwrap(), "16");
+        assert!(serde_json::from_str::<Hearts>("16.5").is_err());
+    }
+}
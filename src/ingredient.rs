@@ -0,0 +1,38 @@
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Cooking ingredient resolved to its actor name, e.g. `Item_Fruit_A`
+///
+/// Display names can be ambiguous ("Fairy", "Hearty Radish"), actor names
+/// are not.  Obtain one from [`crate::Cook::ingredient`]; it can be passed
+/// anywhere a name is accepted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", serde(transparent))]
+pub struct Ingredient {
+    actor: String,
+}
+
+impl Ingredient {
+    pub(crate) fn new(actor: &str) -> Self {
+        Self {
+            actor: actor.to_string(),
+        }
+    }
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+}
+
+impl AsRef<str> for Ingredient {
+    fn as_ref(&self) -> &str {
+        &self.actor
+    }
+}
+
+impl fmt::Display for Ingredient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.actor)
+    }
+}
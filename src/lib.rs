@@ -1,29 +1,104 @@
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use log::{debug, trace};
+
+use matcher::Matcher;
 use thiserror::Error;
 
+mod context;
+mod crit;
+mod describe;
+mod diagnostic;
+pub mod duration;
+mod explain;
+mod fuzzy;
+mod hearts;
+mod ingredient;
+mod locale;
+mod map;
+mod matcher;
 mod modifier;
+mod msbt;
+mod random;
+#[cfg(test)]
+mod reference;
+mod roll;
+pub mod tables;
 mod wmc;
-use modifier::*;
-use wmc::*;
+pub use context::*;
+pub use crit::*;
+pub use diagnostic::*;
+pub use duration::Duration;
+pub use explain::*;
+pub use hearts::Hearts;
+pub use ingredient::*;
+pub use locale::{NameTable, ENGLISH};
+pub use map::Map;
+pub use modifier::*;
+pub use msbt::Msbt;
+pub use random::*;
+pub use roll::*;
+pub use wmc::*;
 
 #[derive(Error, Debug)]
 pub enum CookError {
+    #[cfg(feature = "json")]
     #[error("parse error {0}")]
     Parse(#[from] serde_json::Error),
     #[error("io error {0}")]
     File(#[from] std::io::Error),
-    #[error("Unknown item {0}")]
-    UnknownItem(String),
+    #[error("Unknown item {name}{}", did_you_mean(suggestions))]
+    UnknownItem {
+        name: String,
+        suggestions: Vec<String>,
+    },
+    #[error("Unknown effect {0}")]
+    UnknownEffect(Modifier),
     #[error("Recipe not found")]
     NotFound,
+    #[error("No ingredients, at least 1 is required")]
+    NoIngredients,
+    #[error("Too many ingredients ({0}), at most 5 are allowed")]
+    TooManyIngredients(usize),
+    #[error("Invalid duration {0}, expected mm:ss")]
+    InvalidDuration(String),
+    #[error("No names for language {0}")]
+    UnknownLanguage(String),
+    #[error("No {language} name for {name}")]
+    Untranslated { language: String, name: String },
+    #[error("Invalid MSBT file: {0}")]
+    InvalidMsbt(String),
+}
+
+impl CookError {
+    fn unknown_item(name: &str) -> Self {
+        CookError::UnknownItem {
+            name: name.to_string(),
+            suggestions: vec![],
+        }
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
-#[serde(untagged)]
+fn did_you_mean(suggestions: &[String]) -> String {
+    if suggestions.is_empty() {
+        return String::new();
+    }
+    format!(", did you mean {}?", suggestions.join(", "))
+}
+
+/// Text from the static tables, or read at runtime
+type Str = Cow<'static, str>;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "json", serde(untagged))]
 enum AVec {
-    One(Vec<String>),
-    Two(Vec<Vec<String>>),
+    One(Cow<'static, [Str]>),
+    Two(Cow<'static, [Cow<'static, [Str]>]>),
 }
 impl AVec {
     fn len(&self) -> usize {
@@ -32,100 +107,139 @@ impl AVec {
             Self::Two(v) => v.len(),
         }
     }
-    fn id(&self, i: usize) -> &[String] {
+    fn id(&self, i: usize) -> &[Str] {
         match self {
-            Self::One(v) => &v,
+            Self::One(v) => v,
             Self::Two(v) => &v[i],
         }
     }
-    fn vec(&self) -> Vec<Vec<String>> {
+    /// Groups of alternatives, a flat list being a single group
+    fn groups(&self) -> Vec<&[Str]> {
         match self {
-            Self::One(v) => vec![v.clone()],
-            Self::Two(v) => v.clone(),
+            Self::One(v) if v.is_empty() => vec![],
+            Self::One(v) => vec![v],
+            Self::Two(v) => v.iter().map(|x| x.as_ref()).collect(),
         }
     }
-}
-
-fn inter(a: &[String], b: &[String]) -> Vec<String> {
-    let mut c = vec![];
-    for ai in a {
-        if b.iter().any(|x| x == ai) {
-            c.push(ai.clone())
+    fn vec(&self) -> Vec<Vec<String>> {
+        let strings = |v: &[Str]| v.iter().map(|x| x.to_string()).collect();
+        match self {
+            Self::One(v) => vec![strings(v)],
+            Self::Two(v) => v.iter().map(|x| strings(x)).collect(),
         }
     }
-    c
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
 pub struct RecipeBase {
     hb: i32,
-    name: String,
+    name: Str,
     tags: AVec,
     actors: AVec,
     num: i32,
-    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(default))]
     id: i32,
 }
-#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
 pub struct Recipe {
     pub name: String,
     pub id: i32,
+    // Only read when serialized
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
     actors: Vec<Vec<String>>,
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
     tags: Vec<Vec<String>>,
+    /// English names of the ingredients, see [`Cook::localized_names`]
     pub items: Vec<String>,
-    pub hp: f32,
-    pub time: i32,
+    /// Actors of the ingredients, in the order of `items`
+    #[cfg_attr(feature = "json", serde(default))]
+    pub ingredients: Vec<String>,
+    pub hp: Hearts,
+    pub time: Duration,
     pub potency: i32,
     pub effect_level_name: Potency,
     pub level: i32,
-    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(default))]
     pub effect: Modifier,
-    pub hearts: f32,
     pub price: i32,
-    pub hp_crit: i32,
-    pub time_crit: i32,
+    pub hp_crit: Hearts,
+    pub time_crit: Duration,
     pub level_crit: i32,
     pub crit_rate: i32,
+    /// Monster Extract randomizes hearts and duration, see `extract_outcomes`
     pub monster_rng: bool,
     pub stamina: f32,
     pub stamina_crit: f32,
     pub stamina_extra: f32,
     pub stamina_extra_crit: f32,
     pub wmc: WMC,
+    /// Each possible critical success, empty if a crit cannot happen
+    #[cfg_attr(feature = "json", serde(default))]
+    pub crit_outcomes: Vec<CritOutcome>,
+    /// Each possible Monster Extract result, empty without Monster Extract
+    #[cfg_attr(feature = "json", serde(default))]
+    pub extract_outcomes: Vec<ExtractOutcome>,
+    /// Effects of the ingredients, whether or not they made it into the dish
+    #[cfg_attr(feature = "json", serde(default))]
+    pub effects: Vec<EffectSource>,
+    /// Ingredients had more than one effect, so the dish has none
+    #[cfg_attr(feature = "json", serde(default))]
+    pub effects_cancelled: bool,
+    /// Actor of the dish put in the pouch, e.g. `Item_Cook_A_01`
+    ///
+    /// Every Elixir is `Item_Cook_C_17`, its effect tells them apart.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub actor: String,
+    /// Word put in front of `name` in game, e.g. "Mighty"
+    ///
+    /// Empty without an effect and for Elixirs, whose `name` already
+    /// includes it.
+    #[cfg_attr(feature = "json", serde(default))]
+    pub prefix: String,
 }
 
 impl Recipe {
+    /// Name shown in game, e.g. "Mighty Meat Skewer"
+    pub fn display_name(&self) -> String {
+        if self.prefix.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} {}", self.prefix, self.name)
+        }
+    }
+    /// Set the hearts restored, and the weapon modifiers that depend on them
+    pub(crate) fn set_hp(&mut self, hp: Hearts) {
+        self.hp = hp;
+        self.wmc = WMC::new(self.price, hp.quarters());
+    }
     fn rock_hard_food(items: &[String], r: &RecipeBase) -> Self {
         Self {
             actors: r.actors.vec(),
             tags: r.tags.vec(),
             name: "Rock-Hard Food".to_string(),
-            hp: 1.0,
+            hp: Hearts::from_quarters(1),
             id: if unique_len(items) == 1 { 126 } else { 3 },
-            hearts: 0.25,
             price: 2,
             items: items.to_vec(),
-            hp_crit: 1,
+            hp_crit: Hearts::from_quarters(1),
             level: 1,
             wmc: WMC::new(2, 1),
             ..Default::default()
         }
     }
-    fn dubious_food(hp: f32, items: &[String], r: &RecipeBase) -> Self {
-        let mut hp = hp;
-        if hp < 4.0 {
-            hp = 4.0;
-        }
+    fn dubious_food(hp: Hearts, items: &[String], r: &RecipeBase) -> Self {
+        let hp = hp.max(Hearts::from_hearts(1)).capped();
         Self {
             actors: r.actors.vec(),
             tags: r.tags.vec(),
             name: "Dubious Food".to_string(),
             hp,
             id: r.id,
-            hearts: hp / 4.0,
             price: 2,
             items: items.to_vec(),
-            hp_crit: hp as i32,
+            hp_crit: hp,
             level: 1,
             wmc: WMC::new(2, 2),
             ..Default::default()
@@ -140,13 +254,6 @@ fn unique(items: &[String]) -> Vec<String> {
     }
     h.keys().map(|x| x.to_string()).collect()
 }
-fn unique_mod(items: &[Modifier]) -> Vec<Modifier> {
-    let mut h: HashSet<Modifier> = HashSet::new();
-    for item in items {
-        h.insert(*item);
-    }
-    h.into_iter().collect()
-}
 
 fn unique_len(items: &[String]) -> usize {
     let mut h = HashMap::new();
@@ -157,228 +264,80 @@ fn unique_len(items: &[String]) -> usize {
 }
 
 impl RecipeBase {
-    fn matches(&self, items: &[String], tags: &[String], strict: bool, verbose: bool) -> bool {
-        if verbose {
-            println!("-------------------------------------");
-        }
-        if strict {
-            if verbose {
-                println!("init name {} id {} ", self.name, self.id);
-                println!("strict mode");
-            }
-            let mut v: Vec<String> = items.iter().map(|x| x.to_string()).collect();
-            v.sort_unstable();
-            v.dedup();
-            if v.len() != 1 {
-                if verbose {
-                    println!("Number of unique items != 1");
-                    println!("     items: {:?}", v);
-                }
-                return false;
-            }
-        }
-        let mut items_t = items.to_vec().clone();
-        let mut tags_t = tags.to_vec().clone();
-        if verbose {
-            println!("init name {} id {} ", self.name, self.id);
-            println!("     items: {:?}", items_t);
-            println!("      tags: {:?}", tags_t);
-            println!("    actors: {:?}", self.actors);
-        }
-        let out = self.matches_actors(items_t, tags_t, strict, verbose);
-        if out.is_none() {
-            return false;
-        }
-        let out = out.unwrap();
-        items_t = out.0;
-        tags_t = out.1;
-
-        if verbose {
-            println!("");
-            println!("     items: {:?}", items_t);
-            println!("      tags: {:?}", tags_t);
-            println!("recipe tags: {:?}", self.tags);
-        }
-        let items_t = self.matches_tags(items_t, tags_t, strict, verbose);
-        if verbose {
-            println!("");
-            println!("     items: {:?}", items_t);
-        }
-        if items_t.is_none() {
-            return false;
-        }
-        let items_t = items_t.unwrap();
-        if verbose {
-            println!("done: {} {:?}", self.name, items_t);
-        }
-        if strict {
-            return items_t.len() == 0;
-        }
-        return true;
-    }
-    fn matches_actors(
-        &self,
-        items_t: Vec<String>,
-        tags_t: Vec<String>,
-        strict: bool,
-        verbose: bool,
-    ) -> Option<(Vec<String>, Vec<String>)> {
-        let mut items_t = items_t;
-        let mut tags_t = tags_t;
-        if strict {
-            if self.actors.len() == 0 {
-                if verbose {
-                    println!("No actors, returning current values");
-                }
-                return Some((items_t, tags_t));
-            }
-            let v = inter(&self.actors.id(0), &items_t);
-            if v.len() == 0 {
-                if verbose {
-                    println!("No matching actors, returning empty");
-                }
-                return None;
-            }
-            if verbose {
-                println!("Found matching actors, removing from items {:?}", v);
-            }
-            let v = &v[0];
-            let mut k = items_t.iter().position(|x| &x == &v);
-            while k.is_some() {
-                items_t.remove(k.unwrap());
-                tags_t.remove(k.unwrap());
-                k = items_t.iter().position(|x| &x == &v);
-            }
-            if verbose {
-                println!(
-                    "Found matching actors, removing from items {:?} {:?}",
-                    items_t, tags_t
-                );
-            }
-            return Some((items_t, tags_t));
-        }
-        let n = self.actors.len();
-        if verbose {
-            println!("ACTORS {n}");
-        }
-        for i in 0..n {
-            if verbose {
-                println!("{:?} {:?}", self.actors.id(i), items_t);
-            }
-            let v = inter(self.actors.id(i), &items_t);
-            if v.len() == 0 {
-                return None;
-            }
-            let mut k = items_t.iter().position(|x| x == &v[0]);
-            while k.is_some() {
-                items_t.remove(k.unwrap());
-                tags_t.remove(k.unwrap());
-                k = items_t.iter().position(|x| x == &v[0]);
-            }
-        }
-        return Some((items_t, tags_t));
+    pub fn name(&self) -> &str {
+        &self.name
     }
-    fn matches_tags(
-        &self,
-        items_t: Vec<String>,
-        tags_t: Vec<String>,
-        strict: bool,
-        verbose: bool,
-    ) -> Option<Vec<String>> {
-        let mut items_t = items_t;
-        let mut tags_t = tags_t;
-        if verbose {
-            println!("    item tags: {:?}", tags_t);
-        }
-        if strict {
-            if self.tags.len() == 0 {
-                return Some(items_t);
-            }
-            let v = inter(self.tags.id(0), &tags_t);
-            if v.len() == 0 {
-                return None;
-            }
-            let mut k = tags_t.iter().position(|x| x == &v[0]);
-            while k.is_some() {
-                items_t.remove(k.unwrap());
-                tags_t.remove(k.unwrap());
-                k = tags_t.iter().position(|x| x == &v[0]);
-            }
-            return Some(items_t);
-        }
-
-        let tags = match &self.tags {
-            AVec::Two(v) => v,
-            AVec::One(v) => {
-                if v.len() == 0 {
-                    return Some(items_t);
-                } else {
-                    panic!(":( {:?}", self)
-                }
-            }
-        };
-        let n = tags.len();
-        for i in 0..n {
-            let mut k = None;
-            for j in 0..tags[i].len() {
-                if verbose {
-                    println!("{:?} {:?}, {} {}", tags[i], tags_t, i, j)
-                }
-                k = tags_t.iter().position(|x| x == &tags[i][j]);
-                if k.is_some() {
-                    break;
-                }
-            }
-            if k.is_none() {
-                return None;
-            }
-
-            let item = items_t[k.unwrap()].clone();
-            while k.is_some() {
-                items_t.remove(k.unwrap());
-                tags_t.remove(k.unwrap());
-                k = items_t.iter().position(|x| x == &item);
-            }
-        }
-        Some(items_t)
+    pub fn id(&self) -> i32 {
+        self.id
     }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
 pub struct Effect {
     base_time: i32,
     material_rate: f32,
     max: i32,
     min: i32,
     ssa: i32,
-    #[serde(rename = "type")]
+    #[cfg_attr(feature = "json", serde(rename = "type"))]
     kind: Modifier,
-    xtype: String,
+    xtype: Str,
+}
+
+impl Effect {
+    /// Strength of the effect for the summed `potency` of the ingredients
+    ///
+    /// Counted in levels for most effects, in quarter hearts for LifeMaxUp
+    /// and in fifths of a wheel for GutsRecover and ExGutsMaxUp.
+    pub fn level(&self, potency: i32) -> i32 {
+        ((potency as f32 * self.material_rate) as i32).clamp(self.min, self.max)
+    }
+    /// Potency at which each level above `min` starts
+    pub fn tiers(&self) -> Vec<i32> {
+        // Potency is the sum of at most five ingredients
+        (self.min + 1..=self.max)
+            .filter_map(|level| (1..1000).find(|p| self.level(*p) >= level))
+            .collect()
+    }
 }
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "json", derive(Deserialize, Serialize))]
 pub struct Item {
     hp: i32,
     effect: Modifier,
     potency: i32,
     time: i32,
-    #[serde(rename = "tags")]
-    tags_raw: Vec<String>,
-    #[serde(default)]
-    tags: String,
-    name: String,
+    #[cfg_attr(feature = "json", serde(rename = "tags"))]
+    tags_raw: Cow<'static, [Str]>,
+    // First of `tags_raw`, set on load.  The "tags" key belongs to
+    // `tags_raw`, so this field is never read and must not be written.
+    #[cfg_attr(feature = "json", serde(skip))]
+    tags: Str,
+    name: Str,
     time_boost: i32,
     hp_boost: i32,
     cook_low_price: bool,
     key_item: bool,
     roast_item: bool,
-    #[serde(deserialize_with = "parse_i32")]
+    #[cfg_attr(feature = "json", serde(deserialize_with = "parse_i32"))]
     sell_price: i32,
-    #[serde(deserialize_with = "parse_i32")]
+    #[cfg_attr(feature = "json", serde(deserialize_with = "parse_i32"))]
     buy_price: i32,
-    #[serde(default)]
+    #[cfg_attr(feature = "json", serde(default))]
     boost_success_rate: i32,
+    #[cfg_attr(feature = "json", serde(skip))]
+    id: u16,
+    #[cfg_attr(feature = "json", serde(skip, default = "no_tag"))]
+    tag_id: u16,
 }
+#[cfg(feature = "json")]
+fn no_tag() -> u16 {
+    matcher::NO_TAG
+}
+#[cfg(feature = "json")]
 use serde::Deserializer;
+#[cfg(feature = "json")]
 fn parse_i32<'de, D>(d: D) -> Result<i32, D::Error>
 where
     D: Deserializer<'de>,
@@ -386,190 +345,504 @@ where
     Deserialize::deserialize(d).map(|x: Option<_>| x.unwrap_or(0))
 }
 
+#[cfg(feature = "json")]
 fn read_recipes() -> Result<Vec<RecipeBase>, CookError> {
     Ok(serde_json::from_str(include_str!("cook_recipes.json"))?)
 }
+#[cfg(feature = "json")]
 fn read_items() -> Result<HashMap<String, Item>, CookError> {
     //let fp = File::open(file)?;
     //let buf = BufReader::new(fp);
     Ok(serde_json::from_str(include_str!("cook_items.json"))?)
 }
+#[cfg(feature = "json")]
 fn read_tags() -> Result<Vec<String>, CookError> {
     //let fp = File::open(file)?;
     //let buf = BufReader::new(fp);
     Ok(serde_json::from_str(include_str!("cook_tags.json"))?)
 }
+#[cfg(feature = "json")]
 fn read_names() -> Result<HashMap<String, String>, CookError> {
     //let fp = File::open(file)?;
     //let buf = BufReader::new(fp);
     Ok(serde_json::from_str(include_str!("names.json"))?)
 }
+#[cfg(feature = "json")]
 fn read_effects() -> Result<Vec<Effect>, CookError> {
     //let fp = File::open(file)?;
     //let buf = BufReader::new(fp);
     Ok(serde_json::from_str(include_str!("cook_effects.json"))?)
 }
+#[cfg(feature = "json")]
+fn read_modifiers() -> Result<Vec<ModifierData>, CookError> {
+    Ok(serde_json::from_str(include_str!("cook_modifiers.json"))?)
+}
+
+/// Set the cook tag and matcher ids of each item, adding tags missing
+/// from `tags`
+#[cfg(feature = "json")]
+fn index_items(data: &mut Map<Item>, tags: &mut Vec<Str>) {
+    for (i, item) in data.values_mut().enumerate() {
+        item.id = i as u16;
+        item.tags = item.tags_raw.first().cloned().unwrap_or_default();
+        if !item.tags.is_empty() && !tags.contains(&item.tags) {
+            tags.push(item.tags.clone());
+        }
+        item.tag_id = tags
+            .iter()
+            .position(|t| *t == item.tags)
+            .map_or(matcher::NO_TAG, |i| i as u16);
+    }
+}
+
+/// `Item_Cook_*` actor by dish name, from display names by actor
+#[cfg(feature = "json")]
+fn dish_actors(names: &Map<Str>) -> Map<Str> {
+    names
+        .iter()
+        // Skip stack labels such as "Elixir x[NUMBER]"
+        .filter(|(actor, name)| actor.starts_with("Item_Cook_") && !name.contains('['))
+        .map(|(actor, name)| (name.clone(), Str::from(actor.to_string())))
+        .collect()
+}
+
+/// Tables and indexes a [`Cook`] is built from, borrowed or owned
+struct Data {
+    english: NameTable,
+    items: Map<Item>,
+    recipes: Cow<'static, [RecipeBase]>,
+    effects: Vec<Effect>,
+    modifiers: Vec<ModifierData>,
+    tags: Cow<'static, [Str]>,
+    dish_actors: Map<Str>,
+    matcher: Matcher,
+}
+
+const MONSTER_EXTRACT: &str = "Item_Material_08";
 
 pub struct Cook {
     pub effects: Vec<Effect>,
-    pub names: HashMap<String, String>,
-    pub inames: HashMap<String, String>, // Inverse names
-    pub tags: Vec<String>,
-    pub data: HashMap<String, Item>, // items
-    pub recipes: Vec<RecipeBase>,
+    /// Elixir names and dish prefixes
+    pub modifiers: HashMap<Modifier, ModifierData>,
+    /// Display names by actor, in the selected language
+    pub names: Map<Cow<'static, str>>,
+    pub inames: Map<Cow<'static, str>>, // Inverse names
+    pub tags: Cow<'static, [Cow<'static, str>]>,
+    pub data: Map<Item>, // items
+    pub recipes: Cow<'static, [RecipeBase]>,
     pub price_scale: Vec<f32>,
     pub crit_scale: Vec<i32>,
     dubious: RecipeBase,
+    /// `Item_Cook_*` actor by recipe name
+    dish_actors: Map<Str>,
+    /// `recipes` compiled for fast matching
+    matcher: Matcher,
+    /// Name tables by language
+    languages: HashMap<String, NameTable>,
+    /// Language of `names` and `inames`
+    language: String,
+    /// Problems found in the data while constructing
+    pub diagnostics: Vec<Diagnostic>,
+    /// Last value given to [`Cook::set_verbose`]
+    #[deprecated(note = "traces go through the `log` crate, see `Cook::set_verbose`")]
     pub verbose: bool,
-    //threshold: HashMap<&'static str, [i32; 2]>,
-    //elixirs: HashMap<&'static str, &'static str>,
+}
+
+impl Default for Cook {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Cook {
+    /// Record whether how each dish is cooked should be printed
+    ///
+    /// Only sets [`Cook::verbose`].  The traces go through the `log` crate,
+    /// so whether they are printed is up to the logger and level the
+    /// application sets up, which this leaves alone.
+    #[deprecated(note = "install a `log` logger and set the max level instead")]
     pub fn set_verbose(&mut self, verbose: bool) {
-        self.verbose = verbose
+        #[allow(deprecated)]
+        {
+            self.verbose = verbose;
+        }
     }
     pub fn new() -> Self {
-        let names = read_names().unwrap();
-        let mut data_raw = read_items().unwrap();
-        let mut data = HashMap::new();
-        // reduce_tags()
-        for (name, item) in &mut data_raw {
-            if item.tags_raw.len() > 1 {
-                panic!("Item > 1 cook tag {} {:?}", name, item.tags_raw);
-            }
-            if item.tags_raw.len() == 1 {
-                item.tags = item.tags_raw[0].to_string();
-            }
-            //if item.effect == "" || item.effect == Modifier::None {
-            //    item.effect = "".to_string();
-            //}
-            data.insert(name.to_string(), item.clone());
-        }
-        // set_proper_names()
-        let mut inames = HashMap::new();
-        for key in names.keys() {
-            if data.get(key).is_some() {
-                let value = names.get(key).unwrap().to_string();
-                if inames.get(&value).is_some() && key.starts_with("Animal_") {
-                    continue;
-                }
-                inames.insert(value, key.to_string());
-            }
+        Self::try_new().unwrap()
+    }
+    /// Shared instance, constructed on first use
+    ///
+    /// Panics if the embedded data cannot be read, use [`Cook::try_new`] to
+    /// handle that case.
+    pub fn global() -> &'static Cook {
+        static COOK: OnceLock<Cook> = OnceLock::new();
+        COOK.get_or_init(Cook::new)
+    }
+    /// Construct from the static tables generated at build time
+    ///
+    /// The tables and the indexes over them are built by `build.rs` and
+    /// borrowed, so nothing is parsed, compiled or copied.
+    pub fn try_new() -> Result<Self, CookError> {
+        Self::from_data(Data {
+            english: NameTable {
+                names: tables::NAMES.clone(),
+                actors: tables::NAME_ACTORS.clone(),
+            },
+            items: tables::ITEMS.clone(),
+            recipes: Cow::Borrowed(tables::RECIPES),
+            effects: tables::EFFECTS.to_vec(),
+            modifiers: tables::MODIFIERS.to_vec(),
+            tags: Cow::Borrowed(tables::TAGS),
+            dish_actors: tables::DISH_ACTORS.clone(),
+            matcher: tables::MATCHER.clone(),
+        })
+    }
+    /// Construct by parsing the embedded JSON files at runtime
+    #[cfg(feature = "json")]
+    pub fn from_json() -> Result<Self, CookError> {
+        let mut items: Map<Item> = read_items()?.into();
+        let mut tags: Vec<Str> = read_tags()?.into_iter().map(Str::from).collect();
+        index_items(&mut items, &mut tags);
+        let mut recipes = read_recipes()?;
+        for (i, recipe) in recipes.iter_mut().enumerate() {
+            recipe.id = i as i32;
         }
-        let prefer = [
-            ["Hearty Radish", "Item_PlantGet_B"],
-            ["Big Hearty Radish", "Item_PlantGet_C"],
-            ["Endura Carrot", "Item_PlantGet_Q"],
-            ["Swift Carrot", "Item_PlantGet_M"],
-            ["Silent Princess", "Item_PlantGet_J"],
-            ["Octo Balloon", "Item_Enemy_57"],
-            ["Master Sword", "Item_Sword_080"],
-        ];
-        for [key, value] in prefer {
-            if inames.get(key).is_some() {
-                inames.insert(key.to_string(), value.to_string());
+        let english = NameTable::new(read_names()?, |k| items.contains_key(k));
+        Self::from_data(Data {
+            dish_actors: dish_actors(&english.names),
+            matcher: Matcher::new(&items, &tags, &recipes),
+            english,
+            items,
+            recipes: recipes.into(),
+            effects: read_effects()?,
+            modifiers: read_modifiers()?,
+            tags: tags.into(),
+        })
+    }
+    fn from_data(d: Data) -> Result<Self, CookError> {
+        let mut diagnostics = vec![];
+        for (actor, item) in d.items.iter() {
+            if item.tags_raw.len() > 1 {
+                diagnostics.push(Diagnostic::MultipleCookTags {
+                    actor: actor.to_string(),
+                    tags: item.tags_raw.iter().map(|x| x.to_string()).collect(),
+                });
             }
         }
-        for key in data.keys() {
-            if names.get(key).is_none() {
-                eprintln!("Missing {key} from data {:?}", names.get(key));
+        {
+            // Both are sorted, so walk them together rather than search
+            let mut names = d.english.names.keys().peekable();
+            for actor in d.items.keys() {
+                while names.next_if(|name| *name < actor).is_some() {}
+                if names.peek() != Some(&actor) {
+                    diagnostics.push(Diagnostic::MissingName {
+                        actor: actor.to_string(),
+                    });
+                }
             }
         }
-        let mut recipes = read_recipes().unwrap();
-        for i in 0..recipes.len() {
-            recipes[i].id = i as i32;
+        let mut missing: Vec<&str> = d
+            .recipes
+            .iter()
+            .map(|r| r.name.as_ref())
+            .filter(|name| !d.dish_actors.contains_key(name))
+            .collect();
+        missing.sort();
+        missing.dedup();
+        for recipe in missing {
+            diagnostics.push(Diagnostic::MissingDishActor {
+                recipe: recipe.to_string(),
+            });
         }
-        let dubious = recipes
+        let dubious = d
+            .recipes
             .iter()
             .find(|x| x.name == "Dubious Food")
-            .unwrap()
+            .ok_or(CookError::NotFound)?
             .clone();
-        /*
-        let mut threshold = HashMap::new();
-        threshold.insert("AttackUp", [5, 7]);
-        threshold.insert("DefenseUp", [5, 7]);
-        threshold.insert("ResistCold", [6, 999]);
-        threshold.insert("ResistHot", [6, 999]);
-        threshold.insert("ResistElectric", [4, 6]);
-        threshold.insert("Fireproof", [7, 999]);
-        threshold.insert("MovingSpeed", [5, 7]);
-        threshold.insert("Quietness", [6, 9]);
-        threshold.insert("LifeMaxUp", [999, 999]);
-        threshold.insert("GutsRecover", [999, 999]);
-        threshold.insert("ExGutsMaxUp", [999, 999]);
-        threshold.insert("None", [999, 999]);
-        let mut elixirs = HashMap::new();
-        elixirs.insert("AttackUp", "Mighty Elixir");
-        elixirs.insert("DefenseUp", "Tough Elixir");
-        elixirs.insert("ResistCold", "Spicy Elixir");
-        elixirs.insert("ResistHot", "Chilly Elixir");
-        elixirs.insert("ResistElectric", "Electro Elixir");
-        elixirs.insert("Fireproof", "Fireproof Elixir");
-        elixirs.insert("MovingSpeed", "Hasty Elixir");
-        elixirs.insert("Quietness", "Sneaky Elixir");
-        elixirs.insert("ExGutsMaxUp", "Enduring Elixir");
-        elixirs.insert("GutsRecover", "Energizing Elixir");
-        elixirs.insert("LifeMaxUp", "Hearty Elixir");
-        */
-        Self {
-            effects: read_effects().unwrap(),
-            names,
-            inames,
-            tags: read_tags().unwrap(),
-            data, // items
-            recipes,
+        Ok(Self {
+            effects: d.effects,
+            modifiers: d.modifiers.into_iter().map(|m| (m.kind, m)).collect(),
+            names: d.english.names.clone(),
+            inames: d.english.actors.clone(),
+            tags: d.tags,
+            data: d.items, // items
+            recipes: d.recipes,
             price_scale: vec![0.0, 1.5, 1.8, 2.1, 2.4, 2.8], // Cooking::CookData:NMMR
             crit_scale: vec![5, 10, 15, 20, 25],             // Cooking::CookData::NMSSR
             dubious,
+            dish_actors: d.dish_actors,
+            matcher: d.matcher,
+            languages: HashMap::from([(ENGLISH.to_string(), d.english)]),
+            language: ENGLISH.to_string(),
+            diagnostics,
+            #[allow(deprecated)]
             verbose: false,
-            //threshold,
-            //elixirs,
+        })
+    }
+    /// Resolve an ingredient from either an actor name (`Item_PlantGet_B`)
+    /// or a display name (`Hearty Radish`)
+    ///
+    /// Actor names are checked first so ambiguous display names can be
+    /// bypassed by giving the exact actor.  Display names are matched
+    /// ignoring case, accents and punctuation; unknown names are reported
+    /// with the closest known names as suggestions.
+    pub fn ingredient(&self, name: &str) -> Result<Ingredient, CookError> {
+        if self.data.contains_key(name) {
+            return Ok(Ingredient::new(name));
         }
+        if let Some(actor) = self.inames.get(name) {
+            return Ok(Ingredient::new(actor));
+        }
+        let key = fuzzy::normalize(name);
+        let found = self
+            .inames
+            .iter()
+            .filter(|(iname, _)| fuzzy::normalize(iname) == key)
+            .min();
+        if let Some((_, actor)) = found {
+            return Ok(Ingredient::new(actor));
+        }
+        Err(CookError::UnknownItem {
+            name: name.to_string(),
+            suggestions: fuzzy::suggestions(name, self.inames.keys(), 3),
+        })
+    }
+    pub fn ingredients<S: AsRef<str>>(&self, items: &[S]) -> Result<Vec<Ingredient>, CookError> {
+        items
+            .iter()
+            .map(|item| self.ingredient(item.as_ref()))
+            .collect()
     }
     pub fn item_names(&self, items: &[String]) -> Result<Vec<String>, CookError> {
-        let mut inames = vec![];
-        for item in items {
-            let value = self.inames.get(item);
-            if value.is_none() {
-                return Err(CookError::UnknownItem(item.to_string()));
-            }
-            inames.push(value.unwrap().to_string())
+        Ok(self
+            .ingredients(items)?
+            .into_iter()
+            .map(|x| x.actor().to_string())
+            .collect())
+    }
+    fn check_count(&self, n: usize) -> Result<(), CookError> {
+        if n == 0 {
+            return Err(CookError::NoIngredients);
         }
-        Ok(inames)
+        if n >= self.price_scale.len() {
+            return Err(CookError::TooManyIngredients(n));
+        }
+        Ok(())
     }
 
-    pub fn find_recipe(&self, items: &Vec<String>) -> RecipeBase {
-        let iname: Vec<String> = self.item_names(&items).unwrap();
-        let tags_t: Vec<String> = iname
+    pub fn find_recipe(&self, items: &[String]) -> RecipeBase {
+        self.try_find_recipe(items).unwrap()
+    }
+    pub fn try_find_recipe<S: AsRef<str>>(&self, items: &[S]) -> Result<RecipeBase, CookError> {
+        self.check_count(items.len())?;
+        let items: Vec<String> = items.iter().map(|x| x.as_ref().to_string()).collect();
+        let iname: Vec<String> = self.item_names(&items)?;
+        self.find_recipe_actors(&iname)
+    }
+    fn find_recipe_actors(&self, iname: &[String]) -> Result<RecipeBase, CookError> {
+        let mut tags_t: Vec<String> = vec![];
+        let mut ids = vec![];
+        for key in iname {
+            let item = self
+                .data
+                .get(key.as_str())
+                .ok_or_else(|| CookError::unknown_item(key))?;
+            tags_t.push(item.tags.to_string());
+            ids.push((item.id, item.tag_id));
+        }
+        let r = match self.matcher.find(&ids) {
+            Some(i) => &self.recipes[i],
+            None => &self.dubious,
+        };
+        // Every recipe tried is listed by `explain`
+        trace!(id = r.id, name = r.name.as_ref(), actors:? = iname, tags:? = tags_t; "recipe");
+        Ok(r.clone())
+    }
+    /// Every recipe tried for `items`, and why each one did or did not match
+    pub fn explain<S: AsRef<str>>(&self, items: &[S]) -> Result<Explanation, CookError> {
+        self.check_count(items.len())?;
+        let items: Vec<String> = items.iter().map(|x| x.as_ref().to_string()).collect();
+        let actors = self.item_names(&items)?;
+        let tags: Vec<String> = actors
             .iter()
-            .map(|key| self.data.get(key.as_str()).unwrap().tags.clone())
+            .map(|x| self.data[x].tags.to_string())
             .collect();
-        let n = 125;
-        //let i = N;
-        for recipe in &self.recipes[n..] {
-            if recipe.matches(&iname, &tags_t, true, self.verbose) {
-                return recipe.clone();
+        let mut attempts = vec![];
+        let n = matcher::STRICT;
+        let passes = [(&self.recipes[n..], true), (&self.recipes[..n], false)];
+        for (recipes, strict) in passes {
+            for recipe in recipes {
+                let attempt = recipe.attempt(&actors, &tags, strict);
+                trace!(id = attempt.id, name = attempt.name.as_str(), pass:? = attempt.pass,
+                       consumed:? = attempt.consumed, failure:? = attempt.failure; "attempt");
+                let done = attempt.matched();
+                attempts.push(attempt);
+                if done {
+                    return Ok(Explanation {
+                        items: self.display_names(&actors),
+                        actors,
+                        attempts,
+                        id: recipe.id,
+                        name: recipe.name.to_string(),
+                        fallback: false,
+                    });
+                }
             }
         }
-        for recipe in &self.recipes[..n] {
-            if recipe.matches(&iname, &tags_t, false, self.verbose) {
-                return recipe.clone();
+        Ok(Explanation {
+            items: self.display_names(&actors),
+            actors,
+            attempts,
+            id: self.dubious.id,
+            name: self.dubious.name.to_string(),
+            fallback: true,
+        })
+    }
+    /// English names of `actors`, as used in a [`Recipe`]
+    fn display_names(&self, actors: &[String]) -> Vec<String> {
+        let names = &self.languages[ENGLISH].names;
+        actors
+            .iter()
+            .map(|actor| {
+                names
+                    .get(actor)
+                    .map_or(actor.as_str(), |x| x.as_ref())
+                    .to_string()
+            })
+            .collect()
+    }
+    pub fn get_effect(&self, name: Modifier) -> &Effect {
+        self.try_get_effect(name).unwrap()
+    }
+    pub fn try_get_effect(&self, name: Modifier) -> Result<&Effect, CookError> {
+        self.effects
+            .iter()
+            .find(|eff| eff.kind == name)
+            .ok_or(CookError::UnknownEffect(name))
+    }
+    /// Replace the effect records of the effects listed in `reader`
+    ///
+    /// `reader` holds JSON in the format of `cook_effects.json`; effects not
+    /// listed keep their current record.
+    #[cfg(feature = "json")]
+    pub fn load_effects<R: std::io::Read>(&mut self, reader: R) -> Result<(), CookError> {
+        let effects: Vec<Effect> = serde_json::from_reader(reader)?;
+        for e in effects {
+            match self.effects.iter_mut().find(|x| x.kind == e.kind) {
+                Some(x) => *x = e,
+                None => self.effects.push(e),
             }
         }
-        self.dubious.clone()
+        Ok(())
     }
-    pub fn get_effect(&self, name: Modifier) -> &Effect {
-        self.effects.iter().find(|eff| eff.kind == name).unwrap()
+    /// Replace the elixir names and prefixes listed in `reader`
+    ///
+    /// `reader` holds JSON in the format of `cook_modifiers.json`; effects
+    /// not listed are unchanged, as are the fields an entry leaves out.
+    #[cfg(feature = "json")]
+    pub fn load_modifiers<R: std::io::Read>(&mut self, reader: R) -> Result<(), CookError> {
+        let modifiers: Vec<ModifierOverride> = serde_json::from_reader(reader)?;
+        for m in modifiers {
+            let data = self.modifiers.entry(m.kind).or_insert(ModifierData {
+                kind: m.kind,
+                elixir: Cow::Borrowed(""),
+                prefix: Cow::Borrowed(""),
+            });
+            if let Some(elixir) = m.elixir {
+                data.elixir = elixir.into();
+            }
+            if let Some(prefix) = m.prefix {
+                data.prefix = prefix.into();
+            }
+        }
+        Ok(())
+    }
+    /// Potency at which each level above 1 of `effect` starts, from the
+    /// effect record
+    ///
+    /// Empty for effects whose strength is not a level: LifeMaxUp,
+    /// GutsRecover and ExGutsMaxUp.
+    pub fn tiers(&self, effect: Modifier) -> Vec<i32> {
+        match effect {
+            Modifier::LifeMaxUp | Modifier::GutsRecover | Modifier::ExGutsMaxUp => vec![],
+            _ => self
+                .try_get_effect(effect)
+                .map(|e| e.tiers())
+                .unwrap_or_default(),
+        }
+    }
+    /// `Item_Cook_*` actor of the dish named `name`, as in the recipe table
+    pub fn dish_actor(&self, name: &str) -> Option<&str> {
+        self.dish_actors.get(name).map(|x| x.as_ref())
+    }
+    /// Recipe name of the dish with actor `actor`, e.g. `Item_Cook_A_01`
+    pub fn dish_name(&self, actor: &str) -> Option<&str> {
+        self.dish_actors
+            .iter()
+            .find(|(_, a)| *a == actor)
+            .map(|(name, _)| name)
+    }
+    /// Word put in front of the name of a dish with `effect`, empty if none
+    pub fn prefix(&self, effect: Modifier) -> &str {
+        self.modifiers
+            .get(&effect)
+            .map(|m| m.prefix.as_ref())
+            .unwrap_or_default()
+    }
+    /// Name of the Elixir with `effect`, empty if there is none
+    pub fn elixir(&self, effect: Modifier) -> &str {
+        self.modifiers
+            .get(&effect)
+            .map(|m| m.elixir.as_ref())
+            .unwrap_or_default()
     }
     pub fn item(&self, name: &str) -> &Item {
-        self.data.get(self.inames.get(name).unwrap()).unwrap()
+        self.try_item(name).unwrap()
+    }
+    pub fn try_item(&self, name: &str) -> Result<&Item, CookError> {
+        let ingredient = self.ingredient(name)?;
+        self.data
+            .get(ingredient.actor())
+            .ok_or_else(|| CookError::unknown_item(name))
+    }
+    /// Effects `items` would carry into a dish, and which ingredients carry
+    /// each; more than one and they cancel out
+    pub fn effect_sources<S: AsRef<str>>(
+        &self,
+        items: &[S],
+    ) -> Result<Vec<EffectSource>, CookError> {
+        let vals = items
+            .iter()
+            .map(|name| self.try_item(name.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(modifier::effect_sources(&vals))
     }
     pub fn cook<S: AsRef<str>>(&self, items: &[S]) -> Recipe {
+        self.try_cook(items).unwrap()
+    }
+    /// Cook `items`
+    ///
+    /// Hearts and duration are those before any random outcome; critical
+    /// successes and Monster Extract results are listed in `crit_outcomes`
+    /// and `extract_outcomes`.
+    pub fn try_cook<S: AsRef<str>>(&self, items: &[S]) -> Result<Recipe, CookError> {
+        self.try_cook_with(items, &CookContext::default())
+    }
+    /// Cook `items` in the circumstances given by `ctx`
+    pub fn try_cook_with<S: AsRef<str>>(
+        &self,
+        items: &[S],
+        ctx: &CookContext,
+    ) -> Result<Recipe, CookError> {
+        self.check_count(items.len())?;
         let items: Vec<String> = items.iter().map(|x| x.as_ref().to_string()).collect();
-        let r = self.find_recipe(&items);
+        let actors = self.item_names(&items)?;
+        let r = self.find_recipe_actors(&actors)?;
+        // Report ingredients by their English names, whichever form was given
+        let items = self.display_names(&actors);
 
-        let monster_rng = items.contains(&"Monster Extract".to_string())
+        let monster_rng = actors.iter().any(|x| x == MONSTER_EXTRACT)
             && r.name != "Dubious Food"
             && r.name != "Rock-Hard Food";
 
@@ -577,17 +850,17 @@ impl Cook {
         let mut hp = 0;
         let mut potency = 0;
         let mut time = 0;
-        let mut effects = vec![];
         let mut sell_price = 0;
         let mut buy_price = 0;
-        for name in &items {
-            let val = self.item(&name);
+        let vals = actors
+            .iter()
+            .map(|name| self.try_item(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        for val in &vals {
             let has_effect = val.effect != Modifier::None;
             if has_effect {
-                let eff = self.get_effect(val.effect);
-                if self.verbose {
-                    println!("effect {} {}", val.effect, eff.base_time);
-                }
+                let eff = self.try_get_effect(val.effect)?;
+                trace!(effect:% = val.effect, base_time = eff.base_time; "effect time");
                 time += eff.base_time;
             }
             if val.roast_item {
@@ -599,9 +872,6 @@ impl Cook {
                 potency += val.potency;
             }
             hp += val.hp;
-            if has_effect {
-                effects.push(val.effect.clone());
-            }
             if val.cook_low_price {
                 sell_price += 1;
                 buy_price += 1;
@@ -611,213 +881,749 @@ impl Cook {
             }
         }
         hp *= life_rate;
-        if self.verbose {
-            println!("pre scaled sell_price: {sell_price}");
-        }
         let sp_scale32 = sell_price as f32 * self.price_scale[items.len()];
-        if self.verbose {
-            println!("    scaled sell_price: {sp_scale32}");
-        }
-        sell_price = ((sp_scale32.floor() / 10.).ceil() * 10.) as i32;
-        if self.verbose {
-            println!("    scaled sell_price: {sell_price} buy price: {buy_price}");
-        }
+        let scaled = ((sp_scale32.floor() / 10.).ceil() * 10.) as i32;
+        debug!(sell_price, scale = self.price_scale[items.len()], scaled_f32 = sp_scale32,
+               scaled, buy_price; "price");
+        sell_price = scaled;
 
         // Selling price is capped at buying price and a limited to a min of 2
         sell_price = sell_price.max(2);
         sell_price = sell_price.min(buy_price);
 
-        effects = unique_mod(&effects);
-        let mut effect = if effects.len() == 1 {
-            effects[0]
-        } else {
-            Modifier::None
+        let effects = modifier::effect_sources(&vals);
+        let effects_cancelled = effects.len() > 1;
+        let mut effect = match effects.as_slice() {
+            [one] => one.effect,
+            _ => Modifier::None,
         };
-        let thresh = effect.threshold();
-
-        let potency_level;
-        let effect_level;
-        if potency >= thresh[1] {
-            potency_level = Potency::High;
-            effect_level = 3;
-        } else if potency >= thresh[0] {
-            potency_level = Potency::Mid;
-            effect_level = 2;
-        } else {
-            potency_level = Potency::Low;
-            effect_level = 1;
-        }
-        if self.verbose {
-            println!(" effect_level {effect_level} potency_level {potency_level}")
+        if effects_cancelled {
+            debug!(effects:? = effects; "effects cancel");
         }
+        let strength = self
+            .try_get_effect(effect)
+            .map(|e| e.level(potency))
+            .unwrap_or(1);
+        let (effect_level, potency_level) = match effect {
+            Modifier::LifeMaxUp | Modifier::GutsRecover | Modifier::ExGutsMaxUp => {
+                (1, Potency::Low)
+            }
+            _ => (strength, Potency::from_level(strength)),
+        };
+        debug!(effect:% = effect, potency, strength, effect_level,
+               potency_level:% = potency_level; "potency");
 
-        let time_boost: i32 = items
-            .iter()
-            .map(|item| self.item(item))
-            .map(|item| item.time_boost)
-            .sum();
+        let time_boost: i32 = vals.iter().map(|item| item.time_boost).sum();
 
-        let hp_boost: i32 = unique(&items)
-            .iter()
-            .map(|item| self.item(item))
-            .map(|item| item.hp_boost)
-            .sum();
-        if self.verbose {
-            println!("time boost {time} + {time_boost}");
-            println!("hp   boost {hp} + {hp_boost} + {}", r.hb);
-            println!(
-                "{:?}",
-                unique(&items)
-                    .iter()
-                    .map(|item| self.item(item))
-                    .collect::<Vec<_>>()
-            );
-        }
-
-        let crits: Vec<_> = items
+        let hp_boost: i32 = unique(&actors)
             .iter()
-            .map(|item| self.item(item))
-            .map(|item| item.boost_success_rate)
-            .collect();
+            .map(|item| self.try_item(item).map(|item| item.hp_boost))
+            .sum::<Result<i32, _>>()?;
+        debug!(time, time_boost; "time");
+        debug!(hp, hp_boost, recipe_boost = r.hb; "hp");
+
+        let crits: Vec<_> = vals.iter().map(|item| item.boost_success_rate).collect();
         let mut crit_rate = *crits.iter().max().unwrap();
-        crit_rate += self.crit_scale[unique_len(&items) - 1];
+        crit_rate += self.crit_scale[unique_len(&actors) - 1];
         crit_rate = std::cmp::min(crit_rate, 100);
-        if self.verbose {
-            println!(
-                "crits {crit_rate} {:?} {}",
-                crits,
-                self.crit_scale[unique_len(&items) - 1]
-            );
-        }
-
-        if r.name == "Rock-Hard Food" {
-            return Recipe::rock_hard_food(&items, &r);
-        }
-        if r.name == "Dubious Food" {
-            hp = items
-                .iter()
-                .map(|item| self.item(item))
-                .map(|item| item.hp)
-                .sum();
-            if hp <= 0 {
-                hp = 4;
-            }
-            return Recipe::dubious_food(hp as f32, &items, &r);
+        debug!(crit_rate, boosts:? = crits,
+               unique_scale = self.crit_scale[unique_len(&actors) - 1]; "crit");
+        crit_rate = ctx.crit_rate(crit_rate);
+        debug!(crit_rate, blood_moon = ctx.blood_moon; "crit context");
+
+        if r.name == "Rock-Hard Food" || r.name == "Dubious Food" {
+            let mut out = if r.name == "Rock-Hard Food" {
+                Recipe::rock_hard_food(&items, &r)
+            } else {
+                hp = vals.iter().map(|item| item.hp).sum();
+                if hp <= 0 {
+                    hp = 4;
+                }
+                Recipe::dubious_food(Hearts::from_quarters(hp), &items, &r)
+            };
+            out.actor = self.dish_actor(&r.name).unwrap_or_default().to_string();
+            out.ingredients = actors;
+            out.effects = effects;
+            out.effects_cancelled = effects_cancelled;
+            return Ok(out);
         }
         if r.name == "Fairy Tonic" {
             sell_price = 2;
             effect = Modifier::None;
         }
-        hp = hp + hp_boost + r.hb;
+        let hp = Hearts::from_quarters(hp + hp_boost + r.hb).capped();
+        let actor = self.dish_actor(&r.name).unwrap_or_default().to_string();
         let mut out = Recipe {
-            name: r.name,
+            name: r.name.into_owned(),
             id: r.id,
             actors: r.actors.vec(),
             tags: r.tags.vec(),
             items,
-            hp: hp.min(120) as f32,
-            time: time + time_boost,
+            ingredients: actors.clone(),
+            hp,
+            time: Duration::from_secs(time + time_boost),
             potency,
             effect_level_name: potency_level,
             level: std::cmp::min(effect_level, 3),
-            effect: effect,
-            hearts: hp as f32 / 4.0,
+            effect,
             price: sell_price,
-            hp_crit: hp + 3 * 4,                            // Assumes +3 hearts
-            time_crit: time + 5 * 60,                       // Assumes +05:00 duration
-            level_crit: std::cmp::min(effect_level + 1, 3), // Assumes +1 potency tier
+            hp_crit: Hearts::ZERO,
+            time_crit: Duration::ZERO,
+            level_crit: 0,
             crit_rate,
             stamina: 0.0,
             stamina_crit: 0.0,
             stamina_extra: 0.0,
             stamina_extra_crit: 0.0,
             monster_rng,
-            wmc: WMC::new(sell_price, hp as i32),
+            wmc: WMC::new(sell_price, hp.quarters()),
+            crit_outcomes: vec![],
+            extract_outcomes: vec![],
+            effects,
+            effects_cancelled,
+            actor,
+            prefix: String::new(),
         };
 
-        out.time = out.time.min(30 * 60);
-        out.time_crit = out.time_crit.min(30 * 60);
+        out.time = out.time.capped();
 
         if out.effect == Modifier::LifeMaxUp {
-            out.hp = 0.;
-            out.hearts = 0.;
-            out.level = out.potency / 4;
-            out.level_crit = out.level + 1;
-            out.wmc = WMC::new(out.price, out.hp as i32);
+            out.set_hp(Hearts::ZERO);
+            // Level counts whole yellow hearts
+            out.level = strength / 4;
         }
         if out.name == "Elixir" && out.effect != Modifier::None {
-            out.name = out.effect.elixir().to_string();
-        }
-        let crit_stamina = 0.4;
-        if out.effect == Modifier::GutsRecover {
-            let recover = [0.0, 0.2, 0.4, 0.8, 1.0, 1.4, 1.6, 1.8, 2.2, 2.4, 2.8, 3.0];
-            let mut potency = potency as usize;
-            if potency > recover.len() - 1 {
-                potency = recover.len() - 1;
-            };
-            out.stamina = recover[potency];
-            out.stamina_crit = out.stamina + crit_stamina;
-            if out.stamina_crit > 3.0 {
-                out.stamina_crit = 3.0;
-            }
+            out.name = self.elixir(out.effect).to_string();
+        } else {
+            out.prefix = self.prefix(out.effect).to_string();
         }
-        if out.effect == Modifier::ExGutsMaxUp {
-            struct Guts {
-                pts: i32,
-                val: f32,
-            }
-            let recover = vec![
-                Guts { pts: 0, val: 0.0 },
-                Guts { pts: 1, val: 0.2 },
-                Guts { pts: 4, val: 0.4 },
-                Guts { pts: 6, val: 0.6 },
-                Guts { pts: 8, val: 0.8 },
-                Guts { pts: 10, val: 1.0 },
-                Guts { pts: 12, val: 1.2 },
-                Guts { pts: 14, val: 1.4 },
-                Guts { pts: 16, val: 1.6 },
-                Guts { pts: 18, val: 1.8 },
-                Guts { pts: 20, val: 2.0 },
-            ];
-            let potency = potency.max(20);
-            let tmp = recover
-                .iter()
-                .filter(|x| x.pts <= potency)
-                .collect::<Vec<&Guts>>()
-                .pop()
-                .unwrap();
-            out.stamina_extra = tmp.val;
-            out.stamina_extra_crit = out.stamina_extra + crit_stamina;
-            if out.stamina_extra_crit > 2.0 {
-                out.stamina_extra_crit = 2.0;
-            }
+        match out.effect {
+            Modifier::GutsRecover => out.stamina = crit::wheels(strength),
+            Modifier::ExGutsMaxUp => out.stamina_extra = crit::wheels(strength),
+            _ => {}
         }
-        if out.name == "Fairy Tonic" && out.items.contains(&"Monster Extract".to_string()) {
-            // Using the maximum hp value
-            //   - hp can be either 1 or 40 (=28+12)
-            out.hp = out.hp_crit as f32;
-            out.hearts = out.hp / 4.;
-            out.wmc = WMC::new(out.price, out.hp as i32);
+        if out.monster_rng {
+            // Monster Extract replaces the critical success roll
+            out.crit_rate = 0;
+            out.extract_outcomes = self.extract_outcomes(&out);
         }
-        out
+        self.set_crit(&mut out);
+        debug!(name = out.name.as_str(), hp = out.hp.quarters(), time = out.time.secs(), level = out.level,
+               price = out.price, crit_rate = out.crit_rate; "cooked");
+        Ok(out)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "json")]
     use std::fs::File;
+    #[cfg(feature = "json")]
     use std::io::BufReader;
 
     #[test]
     fn basic_type() {
         let c = Cook::new();
-        let r = c.find_recipe(&vec!["Fairy".to_string()]);
+        let r = c.find_recipe(&["Fairy".to_string()]);
         println!("{:?}", r);
-        let r = c.cook(&vec!["Fairy".to_string()]);
+        let r = c.cook(&["Fairy".to_string()]);
         println!("{:?}", r);
     }
     #[test]
+    fn unknown_item() {
+        let c = Cook::new();
+        let r = c.try_cook(&["Apple", "Hearty Raddish"]);
+        assert!(matches!(r, Err(CookError::UnknownItem { name, .. }) if name == "Hearty Raddish"));
+        let r = c.try_find_recipe(&["Apple", "Hearty Raddish"]);
+        assert!(matches!(r, Err(CookError::UnknownItem { .. })));
+        assert!(c.try_cook(&["Apple", "Hearty Radish"]).is_ok());
+    }
+    #[test]
+    fn ingredient_count() {
+        let c = Cook::new();
+        let none: [&str; 0] = [];
+        assert!(matches!(c.try_cook(&none), Err(CookError::NoIngredients)));
+        assert!(c.try_cook(&["Apple"; 5]).is_ok());
+        assert!(matches!(
+            c.try_cook(&["Apple"; 6]),
+            Err(CookError::TooManyIngredients(6))
+        ));
+    }
+    #[test]
+    fn fuzzy_names() {
+        let c = Cook::new();
+        let radish = c.ingredient("Hearty Radish").unwrap();
+        assert_eq!(c.ingredient("hearty radish").unwrap(), radish);
+        assert_eq!(c.ingredient("HEARTY  RADISH").unwrap(), radish);
+        assert_eq!(c.ingredient("fairy").unwrap().actor(), "Animal_Insect_F");
+        match c.ingredient("hearty raddish") {
+            Err(CookError::UnknownItem { suggestions, .. }) => {
+                assert_eq!(suggestions[0], "Hearty Radish")
+            }
+            r => panic!("expected unknown item: {r:?}"),
+        }
+        match c.ingredient("swift carot") {
+            Err(CookError::UnknownItem { suggestions, .. }) => {
+                assert_eq!(suggestions[0], "Swift Carrot")
+            }
+            r => panic!("expected unknown item: {r:?}"),
+        }
+        let e = c.ingredient("swift carot").unwrap_err();
+        assert!(e.to_string().contains("did you mean Swift Carrot"), "{e}");
+    }
+    #[test]
+    fn actor_names() {
+        let c = Cook::new();
+        assert_eq!(
+            c.ingredient("Hearty Radish").unwrap().actor(),
+            "Item_PlantGet_B"
+        );
+        assert_eq!(
+            c.ingredient("Item_Plant_B").unwrap().actor(),
+            "Item_Plant_B"
+        );
+        let a = c.cook(&["Apple", "Hearty Radish"]);
+        let b = c.cook(&["Item_Fruit_A", "Item_PlantGet_B"]);
+        assert_eq!(a.name, b.name);
+        assert_eq!(a.hp, b.hp);
+        assert_eq!(a.items, b.items);
+        let items = c.ingredients(&["Apple", "Item_Plant_B"]).unwrap();
+        let r = c.cook(&items);
+        assert_eq!(r.items, ["Apple", "Hearty Radish"]);
+        // Two actors sharing a name count as two kinds of ingredient
+        let one = c.cook(&["Item_PlantGet_B", "Item_PlantGet_B"]);
+        let two = c.cook(&["Item_Plant_B", "Item_PlantGet_B"]);
+        assert_eq!(two.crit_rate, one.crit_rate + 5);
+    }
+    #[test]
+    fn construction() {
+        let c = Cook::try_new().unwrap();
+        assert!(c.diagnostics.is_empty(), "{:?}", c.diagnostics);
+        let g = Cook::global();
+        assert!(std::ptr::eq(g, Cook::global()));
+        assert_eq!(g.cook(&["Apple"]).name, c.cook(&["Apple"]).name);
+    }
+    #[test]
+    #[cfg(feature = "json")]
+    fn tables_match_json() {
+        let a = Cook::try_new().unwrap();
+        let b = Cook::from_json().unwrap();
+        assert_eq!(a.data, b.data);
+        assert_eq!(a.recipes, b.recipes);
+        assert_eq!(a.effects, b.effects);
+        assert_eq!(a.modifiers, b.modifiers);
+        assert_eq!(a.names, b.names);
+        assert_eq!(a.inames, b.inames);
+        assert_eq!(a.tags, b.tags);
+        assert_eq!(a.languages[ENGLISH], b.languages[ENGLISH]);
+        assert_eq!(a.dish_actors, b.dish_actors);
+        assert_eq!(a.matcher, b.matcher);
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn set_verbose() {
+        let level = log::max_level();
+        let mut c = Cook::new();
+        c.set_verbose(true);
+        assert!(c.verbose);
+        c.set_verbose(false);
+        assert!(!c.verbose);
+        // The application's logging is left alone
+        assert_eq!(log::max_level(), level);
+    }
+    /// Check the matcher and `explain` against the original engine
+    fn check_matcher(c: &Cook, items: &[&str]) {
+        let items: Vec<String> = items.iter().map(|x| x.to_string()).collect();
+        let ids: Vec<_> = items
+            .iter()
+            .map(|x| (c.data[x.as_str()].id, c.data[x.as_str()].tag_id))
+            .collect();
+        let expected = reference::find_recipe(c, &items);
+        let fast = c
+            .matcher
+            .find(&ids)
+            .map_or(c.dubious.id, |i| c.recipes[i].id);
+        assert_eq!(fast, expected, "{items:?}");
+        assert_eq!(c.explain(&items).unwrap().id, expected, "{items:?}");
+    }
+    /// Ingredient lists from a fixed linear congruential sequence
+    fn sample<'a>(actors: &[&'a str], n: usize, count: usize) -> Vec<Vec<&'a str>> {
+        let mut x: u64 = n as u64;
+        let mut next = || {
+            x = x
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            actors[(x >> 33) as usize % actors.len()]
+        };
+        (0..count)
+            .map(|_| (0..n).map(|_| next()).collect())
+            .collect()
+    }
+    #[test]
+    fn matcher_agrees() {
+        let c = Cook::new();
+        let actors: Vec<&str> = c.data.keys().collect();
+        for a in &actors {
+            check_matcher(&c, &[a]);
+            check_matcher(&c, &[a, a]);
+        }
+        for n in 2..=5 {
+            for items in sample(&actors, n, 1000) {
+                check_matcher(&c, &items);
+            }
+        }
+    }
+    /// Every pair of ingredients, and many more longer lists; slow
+    #[test]
+    #[ignore]
+    fn matcher_agrees_exhaustive() {
+        let c = Cook::new();
+        let actors: Vec<&str> = c.data.keys().collect();
+        for a in &actors {
+            for b in &actors {
+                check_matcher(&c, &[a, b]);
+            }
+        }
+        for n in 3..=5 {
+            for items in sample(&actors, n, 20000) {
+                check_matcher(&c, &items);
+            }
+        }
+    }
+    #[test]
+    fn explain() {
+        let c = Cook::new();
+        let e = c.explain(&["Apple", "Apple"]).unwrap();
+        let last = e.attempts.last().unwrap();
+        assert_eq!(e.name, "Simmered Fruit");
+        assert_eq!(last.pass, Pass::Strict);
+        assert_eq!(last.consumed[0].matched, "CookFruit");
+        assert_eq!(last.consumed[0].ingredients, [0, 1]);
+        assert!(matches!(
+            e.attempts[0].failure,
+            Some(Failure::MissingActor { .. })
+        ));
+
+        let e = c.explain(&["Apple", "Raw Meat"]).unwrap();
+        let last = e.attempts.last().unwrap();
+        assert_eq!(last.pass, Pass::Loose);
+        assert!(e.attempts[..e.attempts.len() - 1]
+            .iter()
+            .all(|a| !a.matched()));
+        assert!(e
+            .attempts
+            .iter()
+            .any(|a| matches!(a.failure, Some(Failure::NotSingleIngredient { unique: 2 }))));
+
+        let e = c.explain(&["Amber", "Fairy"]).unwrap();
+        assert_eq!(e.name, "Fairy Tonic");
+        assert_eq!(e.actors, ["Item_Ore_F", "Animal_Insect_F"]);
+
+        for items in [
+            vec!["Acorn", "Flint"],
+            vec!["Hylian Rice", "Apple", "Monster Extract"],
+        ] {
+            let e = c.explain(&items).unwrap();
+            assert_eq!(e.id, c.try_find_recipe(&items).unwrap().id);
+        }
+    }
+    #[test]
+    fn crit_outcomes() {
+        let c = Cook::new();
+        let r = c.cook(&["Apple"]);
+        assert_eq!(r.crit_outcomes.len(), 1);
+        assert_eq!(r.crit_outcomes[0].bonus, CritBonus::Hearts);
+        assert_eq!(r.crit_outcomes[0].probability, 0.05);
+        assert_eq!(
+            r.crit_outcomes[0].recipe.hp,
+            r.hp + Hearts::from_quarters(12)
+        );
+        assert_eq!(r.hp_crit, r.hp + Hearts::from_quarters(12));
+
+        let r = c.cook(&["Mighty Bananas"]);
+        let bonuses: Vec<_> = r.crit_outcomes.iter().map(|x| x.bonus).collect();
+        assert_eq!(
+            bonuses,
+            [CritBonus::Hearts, CritBonus::Potency, CritBonus::Duration]
+        );
+        let total: f32 = r.crit_outcomes.iter().map(|x| x.probability).sum();
+        assert!((total - r.crit_rate as f32 / 100.0).abs() < 1e-6);
+        assert_eq!(r.level_crit, r.level + 1);
+        assert_eq!(r.time_crit, r.time + Duration::from_mins(5));
+
+        // Fireproof tops out at level 2, the potency cannot go up
+        let r = c.cook(&["Sizzlefin Trout"; 5]);
+        assert_eq!(r.level, 2);
+        let bonuses: Vec<_> = r.crit_outcomes.iter().map(|x| x.bonus).collect();
+        assert_eq!(bonuses, [CritBonus::Hearts, CritBonus::Duration]);
+
+        // Without an effect record the potency cannot go up
+        let mut c2 = Cook::new();
+        c2.effects.retain(|e| e.kind != Modifier::AttackUp);
+        let r = c.cook(&["Mighty Bananas"]);
+        assert_eq!(
+            c2.crit_bonuses(&r),
+            [CritBonus::Hearts, CritBonus::Duration]
+        );
+        c2.effects.retain(|e| e.kind != Modifier::GutsRecover);
+        assert!(c2.crit_bonuses(&c.cook(&["Stamella Shroom"])).is_empty());
+
+        // A plain dish at full recovery has nothing left to add
+        let r = c.cook(&["Raw Whole Bird"; 5]);
+        assert_eq!(r.name, "Meat Skewer");
+        assert!(r.hp.is_full());
+        assert!(r.crit_rate > 0);
+        assert!(r.crit_outcomes.is_empty());
+        assert_eq!(r.hp_crit, r.hp);
+
+        // Stamina at the GutsRecover maximum of 3 wheels cannot go up
+        let r = c.cook(&["Staminoka Bass"; 5]);
+        assert_eq!(r.stamina, 3.0);
+        assert!(r.crit_rate > 0);
+        assert!(r.crit_outcomes.is_empty());
+        assert_eq!(r.stamina_crit, r.stamina);
+    }
+    #[test]
+    fn cook_with_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+        let c = Cook::new();
+        let items = ["Mighty Bananas", "Mighty Bananas", "Raw Meat"];
+        let rolls = |seed| {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..2000)
+                .map(|_| c.cook_with_rng(&items, &mut rng).unwrap())
+                .collect::<Vec<_>>()
+        };
+        let a = rolls(7);
+        let b = rolls(7);
+        assert!(a.iter().zip(&b).all(|(a, b)| a.crit == b.crit));
+        let rate = a[0].recipe.crit_rate as f32 / 100.0;
+        let crits = a.iter().filter(|x| x.crit.is_some()).count() as f32 / a.len() as f32;
+        assert!((crits - rate).abs() < 0.03, "{crits} {rate}");
+        let r = c.cook(&items);
+        for roll in &a {
+            match roll.crit {
+                None => assert_eq!(roll.recipe.hp, r.hp),
+                Some(CritBonus::Hearts) => assert_eq!(roll.recipe.hp_crit, r.hp_crit),
+                Some(CritBonus::Potency) => assert_eq!(roll.recipe.level, r.level_crit),
+                Some(CritBonus::Duration) => assert_eq!(roll.recipe.time, r.time_crit),
+            }
+        }
+
+        let mut rng = StdRng::seed_from_u64(1);
+        let items = ["Monster Extract", "Armored Porgy", "Raw Meat"];
+        for _ in 0..100 {
+            let roll = c.cook_with_rng(&items, &mut rng).unwrap();
+            let extract = roll.extract.unwrap();
+            assert!(roll.crit.is_none());
+            assert!(EXTRACT_TIMES.contains(&roll.recipe.time));
+            match extract.hearts.unwrap() {
+                ExtractHearts::Minimum => assert_eq!(roll.recipe.hp, Hearts::from_quarters(1)),
+                ExtractHearts::Maximum => {
+                    assert_eq!(roll.recipe.hp, Hearts::from_quarters(16 + 12))
+                }
+            }
+        }
+    }
+    #[test]
+    fn extract_outcomes() {
+        let c = Cook::new();
+        let r = c.cook(&["Monster Extract", "Armored Porgy", "Raw Meat"]);
+        assert_eq!(r.hp, Hearts::from_quarters(16));
+        assert_eq!(r.crit_rate, 0);
+        assert!(r.crit_outcomes.is_empty());
+        assert_eq!(r.extract_outcomes.len(), 6);
+        let p: f32 = r.extract_outcomes.iter().map(|x| x.probability).sum();
+        assert!((p - 1.0).abs() < 1e-6);
+        for x in &r.extract_outcomes {
+            assert_eq!(Some(x.recipe.time), x.roll.time);
+            assert!(x.recipe.extract_outcomes.is_empty());
+        }
+
+        // No effect, only hearts are picked
+        let r = c.cook(&["Monster Extract", "Raw Meat"]);
+        let hp: Vec<_> = r.extract_outcomes.iter().map(|x| x.recipe.hp).collect();
+        assert_eq!(
+            hp,
+            [r.hp + Hearts::from_quarters(12), Hearts::from_quarters(1)]
+        );
+
+        assert_eq!(r.min_hp(), Hearts::from_quarters(1));
+        assert_eq!(r.expected_hp(), (r.hp.quarters() + 13) as f32 / 2.0);
+
+        let r = c.cook(&["Apple"]);
+        assert!(r.extract_outcomes.is_empty());
+        assert_eq!(r.outcomes().len(), 2);
+        assert_eq!(r.min_hp(), r.hp);
+    }
+    #[test]
+    fn cook_context() {
+        let c = Cook::new();
+        let items = ["Mighty Bananas", "Raw Meat"];
+        let ctx = CookContext::blood_moon();
+        let r = c.try_cook_with(&items, &ctx).unwrap();
+        assert_eq!(r.crit_rate, 100);
+        let p: f32 = r.crit_outcomes.iter().map(|x| x.probability).sum();
+        assert!((p - 1.0).abs() < 1e-6);
+        // Every cook is a critical success, the dish as is cannot happen
+        assert!(r.outcomes().iter().all(|x| x.1.crit_outcomes.is_empty()));
+        let mut rng = rand::rngs::mock::StepRng::new(0, 1 << 40);
+        assert!(c.roll(&items, &ctx, &mut rng).unwrap().crit.is_some());
+
+        let ctx = CookContext {
+            crit_rate: Some(0),
+            ..CookContext::default()
+        };
+        let r = c.try_cook_with(&items, &ctx).unwrap();
+        assert_eq!(r.crit_rate, 0);
+        assert!(r.crit_outcomes.is_empty());
+
+        // Monster Extract still replaces the critical success
+        let items = ["Monster Extract", "Raw Meat"];
+        let r = c.try_cook_with(&items, &CookContext::blood_moon()).unwrap();
+        assert_eq!(r.crit_rate, 0);
+        assert!(!r.extract_outcomes.is_empty());
+    }
+    #[test]
+    #[cfg(feature = "json")]
+    fn sead_random() {
+        #[derive(Deserialize)]
+        struct Seed {
+            seed: u32,
+            state: [u32; 4],
+            u32: Vec<u32>,
+        }
+        #[derive(Deserialize)]
+        struct Draw {
+            max: u32,
+            value: u32,
+        }
+        #[derive(Deserialize)]
+        struct Draws {
+            seed: u32,
+            draws: Vec<Draw>,
+        }
+        #[derive(Deserialize)]
+        struct Vectors {
+            seeds: Vec<Seed>,
+            below: Draws,
+        }
+        let fp = File::open("t/sead_random.json").unwrap();
+        let v: Vectors = serde_json::from_reader(BufReader::new(fp)).unwrap();
+        for s in &v.seeds {
+            let mut rng = SeadRandom::new(s.seed);
+            assert_eq!(rng.state(), s.state, "seed {}", s.seed);
+            let out: Vec<u32> = (0..s.u32.len()).map(|_| rng.next_u32()).collect();
+            assert_eq!(out, s.u32, "seed {}", s.seed);
+        }
+        let mut rng = SeadRandom::new(v.below.seed);
+        for d in &v.below.draws {
+            assert_eq!(rng.below(d.max), d.value);
+        }
+    }
+    /// Snapshot of the modelled draw order, not a check against the game
+    #[test]
+    #[cfg(feature = "json")]
+    fn roll_model_snapshot() {
+        #[derive(Deserialize)]
+        struct CookVector {
+            state: [u32; 4],
+            ingredients: Vec<String>,
+            crit: Option<CritBonus>,
+            extract: Option<ExtractRoll>,
+            hp: Hearts,
+            time: Duration,
+            level: i32,
+        }
+        #[derive(Deserialize)]
+        struct Vectors {
+            model_cooks: Vec<CookVector>,
+        }
+        let fp = File::open("t/sead_random.json").unwrap();
+        let v: Vectors = serde_json::from_reader(BufReader::new(fp)).unwrap();
+        let c = Cook::new();
+        for t in &v.model_cooks {
+            let mut rng = SeadRandom::from_state(t.state);
+            let roll = c.cook_with_rng(&t.ingredients, &mut rng).unwrap();
+            assert_eq!(roll.crit, t.crit, "{:?}", t.state);
+            assert_eq!(roll.extract, t.extract, "{:?}", t.state);
+            assert_eq!(roll.recipe.hp, t.hp, "{:?}", t.state);
+            assert_eq!(roll.recipe.time, t.time, "{:?}", t.state);
+            assert_eq!(roll.recipe.level, t.level, "{:?}", t.state);
+        }
+    }
+    #[test]
+    fn effect_levels() {
+        let c = Cook::new();
+        let level = |m, p| c.get_effect(m).level(p);
+        // Stamina, in fifths of a wheel, as previously tabulated
+        let guts = [1, 2, 4, 5, 7, 8, 9, 11, 12, 14, 15, 15, 15];
+        for (p, v) in guts.iter().enumerate() {
+            assert_eq!(level(Modifier::GutsRecover, p as i32 + 1), *v, "{p}");
+        }
+        let ex_guts = [1, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10];
+        for (p, v) in ex_guts.iter().enumerate() {
+            assert_eq!(level(Modifier::ExGutsMaxUp, p as i32 + 1), *v, "{p}");
+        }
+        assert_eq!(level(Modifier::LifeMaxUp, 12), 12);
+        // Levels of the other effects start at the previously tabulated
+        // thresholds
+        let thresholds: [(Modifier, &[i32]); 8] = [
+            (Modifier::AttackUp, &[5, 7]),
+            (Modifier::DefenseUp, &[5, 7]),
+            (Modifier::ResistCold, &[6]),
+            (Modifier::ResistHot, &[6]),
+            (Modifier::ResistElectric, &[4, 6]),
+            (Modifier::Fireproof, &[7]),
+            (Modifier::MovingSpeed, &[5, 7]),
+            (Modifier::Quietness, &[6, 9]),
+        ];
+        for (m, tiers) in thresholds {
+            assert_eq!(c.tiers(m), tiers, "{m}");
+            for p in 1..40 {
+                let want = 1 + tiers.iter().filter(|t| p >= **t).count() as i32;
+                assert_eq!(level(m, p), want, "{m} {p}");
+            }
+        }
+        assert!(c.tiers(Modifier::LifeMaxUp).is_empty());
+
+        let r = c.cook(&["Endura Carrot"; 5]);
+        assert_eq!(r.stamina_extra, 2.0);
+        // A critical success cannot add past two extra wheels
+        assert_eq!(r.stamina_extra_crit, 2.0);
+        let r = c.cook(&["Endura Carrot"; 4]);
+        assert_eq!(r.stamina_extra, 1.6);
+        assert_eq!(r.stamina_extra_crit, 2.0);
+        let r = c.cook(&["Stamella Shroom"; 5]);
+        assert_eq!(r.stamina, 1.4);
+    }
+    #[test]
+    #[allow(deprecated)]
+    #[cfg(feature = "json")]
+    fn modifier_tables() {
+        // As previously hard coded on Modifier
+        assert_eq!(Modifier::AttackUp.threshold(), [5, 7]);
+        assert_eq!(Modifier::ResistCold.threshold(), [6, 999]);
+        assert_eq!(Modifier::Quietness.threshold(), [6, 9]);
+        assert_eq!(Modifier::LifeMaxUp.threshold(), [999, 999]);
+        assert_eq!(Modifier::None.threshold(), [999, 999]);
+        assert_eq!(Modifier::Fireproof.elixir(), "Fireproof Elixir");
+        assert_eq!(Modifier::LifeRecover.elixir(), "");
+        // Unaffected by overrides on an instance
+        let mut c = Cook::new();
+        let data = r#"[{ "type": "AttackUp", "elixir": "Strong Elixir" }]"#;
+        c.load_modifiers(data.as_bytes()).unwrap();
+        assert_eq!(Modifier::AttackUp.elixir(), "Mighty Elixir");
+    }
+    #[test]
+    #[cfg(feature = "json")]
+    fn load_modifiers() {
+        let mut c = Cook::new();
+        let items = ["Mighty Bananas", "Mighty Thistle"];
+        let r = c.cook(&items);
+        assert_eq!(r.level, 1);
+        assert_eq!(c.tiers(Modifier::AttackUp), [5, 7]);
+
+        let data = r#"[{ "type": "AttackUp", "elixir": "Strong Elixir", "prefix": "Strong" }]"#;
+        c.load_modifiers(data.as_bytes()).unwrap();
+        assert_eq!(c.elixir(Modifier::AttackUp), "Strong Elixir");
+        assert_eq!(c.elixir(Modifier::DefenseUp), "Tough Elixir");
+        assert_eq!(c.cook(&items).display_name(), "Strong Steamed Fruit");
+
+        // Fields left out keep their value
+        let data = r#"[{ "type": "DefenseUp", "elixir": "Armored Elixir" },
+                       { "type": "AttackUp", "prefix": "Mighty" }]"#;
+        c.load_modifiers(data.as_bytes()).unwrap();
+        assert_eq!(c.elixir(Modifier::DefenseUp), "Armored Elixir");
+        assert_eq!(c.prefix(Modifier::DefenseUp), "Tough");
+        assert_eq!(c.elixir(Modifier::AttackUp), "Strong Elixir");
+        assert_eq!(c.prefix(Modifier::AttackUp), "Mighty");
+
+        // Levels follow the effect record
+        let data = r#"[{ "base_time": 20, "material_rate": 1.5, "max": 3, "min": 1,
+                         "ssa": 1, "type": "AttackUp", "xtype": "" }]"#;
+        c.load_effects(data.as_bytes()).unwrap();
+        assert_eq!(c.tiers(Modifier::AttackUp), [2, 2]);
+        let r = c.cook(&items);
+        assert_eq!(r.level, 3);
+
+        // Extra stamina is capped by the record's max, in fifths of a wheel
+        let carrots = ["Endura Carrot"; 5];
+        let r = c.cook(&carrots);
+        assert_eq!((r.stamina_extra, r.stamina_extra_crit), (2.0, 2.0));
+        let data = r#"[{ "base_time": 0, "material_rate": 0.5, "max": 15, "min": 1,
+                         "ssa": 2, "type": "ExGutsMaxUp", "xtype": "" }]"#;
+        c.load_effects(data.as_bytes()).unwrap();
+        let r = c.cook(&carrots);
+        assert_eq!((r.stamina_extra, r.stamina_extra_crit), (2.0, 2.4));
+        assert!(c.load_modifiers("[{".as_bytes()).is_err());
+    }
+    #[test]
+    fn cancelled_effects() {
+        let c = Cook::new();
+        let items = ["Mighty Bananas", "Armored Porgy", "Mighty Thistle"];
+        let r = c.cook(&items);
+        assert_eq!(r.effect, Modifier::None);
+        assert!(r.effects_cancelled);
+        let effects: Vec<_> = r.effects.iter().map(|x| x.effect).collect();
+        assert_eq!(effects, [Modifier::AttackUp, Modifier::DefenseUp]);
+        assert_eq!(r.effects[0].ingredients, [0, 2]);
+        assert_eq!(r.effects[1].ingredients, [1]);
+        assert_eq!(c.effect_sources(&items).unwrap(), r.effects);
+
+        let r = c.cook(&["Mighty Bananas", "Raw Meat"]);
+        assert!(!r.effects_cancelled);
+        assert_eq!(r.effects.len(), 1);
+        assert_eq!(r.effects[0].potency, r.potency);
+    }
+    #[test]
+    #[cfg(feature = "json")]
+    /// Snapshot of the generated names, not a check against the game
+    fn display_names_snapshot() {
+        #[derive(Deserialize)]
+        struct NameTest {
+            ingredients: Vec<String>,
+            name: String,
+        }
+        #[derive(Deserialize)]
+        struct NameTests {
+            tests: Vec<NameTest>,
+        }
+        let c = Cook::new();
+        let fp = File::open("t/display_names.json").unwrap();
+        let tests: NameTests = serde_json::from_reader(BufReader::new(fp)).unwrap();
+        for t in &tests.tests {
+            let r = c.cook(&t.ingredients);
+            assert_eq!(r.display_name(), t.name, "{:?}", t.ingredients);
+        }
+    }
+    #[test]
+    fn dish_actors() {
+        let c = Cook::new();
+        assert!(c
+            .diagnostics
+            .iter()
+            .all(|d| !matches!(d, Diagnostic::MissingDishActor { .. })));
+        for r in c.recipes.iter() {
+            let actor = c.dish_actor(r.name()).unwrap();
+            assert!(actor.starts_with("Item_Cook_"), "{actor}");
+            assert_eq!(c.dish_name(actor), Some(r.name()));
+            assert_eq!(c.names[actor], r.name());
+        }
+        assert_eq!(
+            c.cook(&["Apple"]).actor,
+            c.dish_actor("Simmered Fruit").unwrap()
+        );
+        assert_eq!(c.cook(&["Wood"]).actor, "Item_Cook_O_02");
+        assert_eq!(c.cook(&["Fairy"]).actor, "Item_Cook_C_16");
+        let r = c.cook(&["Cold Darner", "Bokoblin Horn"]);
+        assert_eq!(r.name, "Chilly Elixir");
+        assert_eq!(r.actor, "Item_Cook_C_17");
+        assert_eq!(c.dish_name("Item_Cook_C_17_00"), None);
+    }
+    #[test]
+    #[cfg(feature = "json")]
     fn basic_reading() {
         let _v = read_recipes().unwrap();
         let _v = read_items().unwrap();
@@ -826,10 +1632,11 @@ mod tests {
         let _v = read_effects().unwrap();
     }
 
+    #[cfg(feature = "json")]
     #[derive(Debug, Clone, Deserialize, Serialize)]
     struct RTest {
         hearts: f32,
-        hp: f32,
+        hp: Hearts,
         id: i32,
         ingredients: Vec<String>,
         #[serde(default)]
@@ -837,9 +1644,12 @@ mod tests {
         name: String,
         #[serde(default)]
         img: String,
+        #[serde(default)]
+        time: Option<Duration>,
     }
 
     #[test]
+    #[cfg(feature = "json")]
     fn more_tests() {
         let c = Cook::new();
         let mut k = 0;
@@ -871,14 +1681,17 @@ mod tests {
                         r.name, test.name, test, file
                     );
                 }
-                if r.hp != test.hp || r.price != test.price || r.hearts != test.hearts {
+                if r.hp != test.hp || r.price != test.price || r.hp.hearts() != test.hearts {
                     let _r = c.cook(&test.ingredients);
                     println!("{:?}", test);
                 }
                 assert_eq!(r.hp, test.hp, "{} {} {}", file, i, test.name);
                 assert_eq!(r.id, test.id, "{} {} {}", file, i, test.name);
-                assert_eq!(r.hearts, test.hearts, "{} {} {}", file, i, test.name);
+                assert_eq!(r.hp.hearts(), test.hearts, "{} {} {}", file, i, test.name);
                 assert_eq!(r.price, test.price, "{} {} {}", file, i, test.name);
+                if let Some(time) = test.time {
+                    assert_eq!(r.time, time, "{} {} {}", file, i, test.name);
+                }
                 i += 1;
             }
             k += i;
@@ -0,0 +1,238 @@
+//! Ingredient and dish names by language
+//!
+//! Actor names are the stable key.  Each language maps actors to the names
+//! shown in game.  Only English ships with the crate, from `names.json`;
+//! other languages have to be added from the game's own files with
+//! [`Cook::load_names`] or [`Cook::load_msbt`].  Ingredient names are read
+//! in the selected language, see [`Cook::set_language`].
+//!
+//! A [`Recipe`] is always in English, its `items`, `name` and description
+//! alike.  Its actors name it in the selected language through
+//! [`Cook::localized_names`] and [`Cook::dish_display_name`], but only
+//! English names every dish: in other languages dishes with an effect
+//! prefix and Elixirs have no name.
+use crate::{Cook, CookError, Map, Recipe, Str};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Language of the built in names
+pub const ENGLISH: &str = "en";
+
+/// Actors listed under a display name shared with other actors
+///
+/// `build.rs` applies the same rule to the built in names.
+const PREFER: [&str; 7] = [
+    "Item_PlantGet_B", // Hearty Radish
+    "Item_PlantGet_C", // Big Hearty Radish
+    "Item_PlantGet_Q", // Endura Carrot
+    "Item_PlantGet_M", // Swift Carrot
+    "Item_PlantGet_J", // Silent Princess
+    "Item_Enemy_57",   // Octo Balloon
+    "Item_Sword_080",  // Master Sword
+];
+
+/// Display names of actors in one language
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NameTable {
+    /// Display name by actor
+    pub names: Map<Cow<'static, str>>,
+    /// Actor by display name, for actors that can be cooked
+    pub actors: Map<Cow<'static, str>>,
+}
+
+impl NameTable {
+    /// Table for `names`, with the inverse limited to actors `known` to cook
+    pub fn new(names: HashMap<String, String>, known: impl Fn(&str) -> bool) -> Self {
+        let names: Map<Str> = names.into_iter().map(|(k, v)| (k, Str::from(v))).collect();
+        let mut actors: HashMap<&str, &str> = HashMap::new();
+        for (key, value) in names.iter().filter(|(k, _)| known(k)) {
+            if actors.contains_key(value.as_ref()) && key.starts_with("Animal_") {
+                continue;
+            }
+            actors.insert(value, key);
+        }
+        for actor in PREFER {
+            if let Some(name) = names.get(actor) {
+                if actors.contains_key(name.as_ref()) {
+                    actors.insert(name, actor);
+                }
+            }
+        }
+        let actors = actors
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), Str::from(v.to_string())))
+            .collect();
+        Self { names, actors }
+    }
+    /// Display names by actor, for [`NameTable::new`]
+    fn to_hash_map(&self) -> HashMap<String, String> {
+        self.names
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+}
+
+impl Cook {
+    /// Selected language
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+    /// Languages with names, sorted
+    pub fn languages(&self) -> Vec<&str> {
+        let mut out: Vec<&str> = self.languages.keys().map(|x| x.as_str()).collect();
+        out.sort();
+        out
+    }
+    /// Read ingredient names and name actors in `language`
+    ///
+    /// Actors without a name in `language` keep their English name.
+    pub fn set_language(&mut self, language: &str) -> Result<(), CookError> {
+        if !self.languages.contains_key(language) {
+            return Err(CookError::UnknownLanguage(language.to_string()));
+        }
+        self.language = language.to_string();
+        let english = &self.languages[ENGLISH];
+        let table = if language == ENGLISH {
+            english.clone()
+        } else {
+            let mut names = english.to_hash_map();
+            names.extend(self.languages[language].to_hash_map());
+            NameTable::new(names, |k| self.data.contains_key(k))
+        };
+        self.names = table.names;
+        self.inames = table.actors;
+        Ok(())
+    }
+    /// Add the display names in `names`, by actor, to `language`
+    ///
+    /// Names already known for an actor are replaced.
+    pub fn add_names(&mut self, language: &str, names: HashMap<String, String>) {
+        let mut all = self
+            .languages
+            .remove(language)
+            .map(|t| t.to_hash_map())
+            .unwrap_or_default();
+        all.extend(names);
+        let table = NameTable::new(all, |k| self.data.contains_key(k));
+        self.languages.insert(language.to_string(), table);
+        if self.language == language {
+            // Cannot fail, the language was just added
+            let _ = self.set_language(language);
+        }
+    }
+    /// Add the display names in `reader`, formatted as `names.json`, to `language`
+    #[cfg(feature = "json")]
+    pub fn load_names<R: std::io::Read>(
+        &mut self,
+        language: &str,
+        reader: R,
+    ) -> Result<(), CookError> {
+        let names: HashMap<String, String> = serde_json::from_reader(reader)?;
+        self.add_names(language, names);
+        Ok(())
+    }
+    /// Display name of `actor` in the selected language
+    pub fn name(&self, actor: &str) -> Option<&str> {
+        self.names.get(actor).map(|x| x.as_ref())
+    }
+    /// Display names of `actors` in the selected language, e.g. the
+    /// `ingredients` of a [`Recipe`]
+    pub fn localized_names<S: AsRef<str>>(&self, actors: &[S]) -> Vec<String> {
+        actors
+            .iter()
+            .map(|a| self.name(a.as_ref()).unwrap_or(a.as_ref()).to_string())
+            .collect()
+    }
+    /// Name of the dish `r` in the selected language
+    ///
+    /// Outside English only dishes named after their actor are known: the
+    /// wording of effect names such as "Mighty" or "Hearty Elixir" is not,
+    /// and those fail with [`CookError::Untranslated`].
+    pub fn dish_display_name(&self, r: &Recipe) -> Result<String, CookError> {
+        let name = r.display_name();
+        if self.language == ENGLISH {
+            return Ok(name);
+        }
+        if self.languages[ENGLISH]
+            .names
+            .get(&r.actor)
+            .map(|x| x.as_ref())
+            != Some(name.as_str())
+        {
+            return Err(CookError::Untranslated {
+                language: self.language.clone(),
+                name,
+            });
+        }
+        Ok(self.name(&r.actor).unwrap_or(&name).to_string())
+    }
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages() {
+        let mut c = Cook::new();
+        assert_eq!(c.language(), ENGLISH);
+        assert_eq!(c.languages(), [ENGLISH]);
+        assert!(matches!(
+            c.set_language("de"),
+            Err(CookError::UnknownLanguage(_))
+        ));
+
+        let names = r#"{ "Item_Fruit_A": "Apfel", "Item_PlantGet_B": "Energiewurzel",
+                         "Item_Cook_B_02": "Gedünstete Früchte" }"#;
+        c.load_names("de", names.as_bytes()).unwrap();
+        assert_eq!(c.languages(), ["de", ENGLISH]);
+        // Names are only used once selected
+        assert!(c.ingredient("Apfel").is_err());
+
+        c.set_language("de").unwrap();
+        assert_eq!(c.ingredient("Apfel").unwrap().actor(), "Item_Fruit_A");
+        assert_eq!(
+            c.ingredient("energiewurzel").unwrap().actor(),
+            "Item_PlantGet_B"
+        );
+        assert_eq!(
+            c.ingredient("Item_Fruit_A").unwrap().actor(),
+            "Item_Fruit_A"
+        );
+        // Untranslated actors keep their English name
+        assert_eq!(c.ingredient("Wildberry").unwrap().actor(), "Item_Fruit_B");
+        assert!(c.ingredient("Apple").is_err());
+
+        // The recipe stays in English, its actors are named in German
+        let r = c.cook(&["Apfel", "Wildberry"]);
+        assert_eq!(r.name, "Simmered Fruit");
+        assert_eq!(r.items, ["Apple", "Wildberry"]);
+        assert_eq!(r.ingredients, ["Item_Fruit_A", "Item_Fruit_B"]);
+        assert_eq!(c.localized_names(&r.ingredients), ["Apfel", "Wildberry"]);
+        assert_eq!(c.name(&r.actor), Some("Gedünstete Früchte"));
+        assert_eq!(c.dish_display_name(&r).unwrap(), "Gedünstete Früchte");
+        // Effect wording is only known in English
+        let r = c.cook(&["Mighty Bananas"]);
+        assert!(matches!(
+            c.dish_display_name(&r),
+            Err(CookError::Untranslated { .. })
+        ));
+
+        c.add_names(
+            "de",
+            [("Item_Fruit_B", "Waldbeere")]
+                .map(|(a, n)| (a.into(), n.into()))
+                .into(),
+        );
+        let r = c.cook(&["Apfel", "Waldbeere"]);
+        assert_eq!(c.localized_names(&r.ingredients), ["Apfel", "Waldbeere"]);
+
+        c.set_language(ENGLISH).unwrap();
+        let r = c.cook(&["Apple", "Wildberry"]);
+        assert_eq!(c.localized_names(&r.ingredients), ["Apple", "Wildberry"]);
+        assert_eq!(c.name("Item_PlantGet_B"), Some("Hearty Radish"));
+        let r = c.cook(&["Mighty Bananas"]);
+        assert_eq!(c.dish_display_name(&r).unwrap(), "Mighty Simmered Fruit");
+    }
+}
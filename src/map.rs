@@ -0,0 +1,120 @@
+//! Lookup tables that can borrow the static data
+#[cfg(feature = "json")]
+use serde::{Serialize, Serializer};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Map with text keys, sorted for binary search
+///
+/// Borrows the tables in [`crate::tables`] for the built in data, so
+/// [`crate::Cook::try_new`] copies nothing, and owns its entries when they
+/// are read at runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Map<V: Clone + 'static>(Cow<'static, [(Cow<'static, str>, V)]>);
+
+impl<V: Clone + 'static> Map<V> {
+    /// Map over `entries`, which must be sorted by key without duplicates
+    pub const fn from_static(entries: &'static [(Cow<'static, str>, V)]) -> Self {
+        Self(Cow::Borrowed(entries))
+    }
+    /// Position of `key` among the keys, in sorted order
+    pub fn position(&self, key: &str) -> Option<usize> {
+        self.0.binary_search_by(|(k, _)| (**k).cmp(key)).ok()
+    }
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.position(key).map(|i| &self.0[i].1)
+    }
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.position(key).is_some()
+    }
+    /// Entries sorted by key
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.0.iter().map(|(k, v)| (k.as_ref(), v))
+    }
+    /// Keys in sorted order
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(|(k, _)| k.as_ref())
+    }
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.0.iter().map(|(_, v)| v)
+    }
+    /// Values, mutable, copying borrowed entries first
+    #[cfg_attr(not(feature = "json"), allow(dead_code))]
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut V> {
+        self.0.to_mut().iter_mut().map(|(_, v)| v)
+    }
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<V: Clone + 'static> Default for Map<V> {
+    fn default() -> Self {
+        Self(Cow::Borrowed(&[]))
+    }
+}
+
+/// Later entries replace earlier ones with the same key
+impl<K: Into<Cow<'static, str>>, V: Clone + 'static> FromIterator<(K, V)> for Map<V> {
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        let mut entries: Vec<(Cow<'static, str>, V)> =
+            iter.into_iter().map(|(k, v)| (k.into(), v)).collect();
+        // Stable, so the last of equal keys is the one kept
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut out: Vec<(Cow<'static, str>, V)> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match out.last_mut() {
+                Some(last) if last.0 == entry.0 => *last = entry,
+                _ => out.push(entry),
+            }
+        }
+        Self(Cow::Owned(out))
+    }
+}
+
+impl<V: Clone + 'static> From<HashMap<String, V>> for Map<V> {
+    fn from(v: HashMap<String, V>) -> Self {
+        v.into_iter().collect()
+    }
+}
+
+impl<V: Clone + 'static> std::ops::Index<&str> for Map<V> {
+    type Output = V;
+    fn index(&self, key: &str) -> &V {
+        self.get(key).expect("key not in map")
+    }
+}
+
+#[cfg(feature = "json")]
+impl<V: Clone + Serialize + 'static> Serialize for Map<V> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.collect_map(self.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let m: Map<i32> = [("b", 1), ("a", 2), ("b", 3)].into_iter().collect();
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.keys().collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(m.get("b"), Some(&3));
+        assert_eq!(m["a"], 2);
+        assert_eq!(m.position("b"), Some(1));
+        assert!(!m.contains_key("c"));
+
+        static ENTRIES: [(Cow<str>, i32); 2] = [(Cow::Borrowed("x"), 1), (Cow::Borrowed("y"), 2)];
+        static M: Map<i32> = Map::from_static(&ENTRIES);
+        let mut m = M.clone();
+        assert!(matches!(m.0, Cow::Borrowed(_)));
+        m.values_mut().for_each(|v| *v += 1);
+        assert_eq!(m["y"], 3);
+        assert_eq!(M["y"], 2);
+    }
+}
@@ -0,0 +1,231 @@
+//! Recipe matching over interned actor and tag ids
+//!
+//! Produces the same result as `RecipeBase::matches` tried over every
+//! recipe, but compiles the recipe table once so a lookup is a handful of
+//! integer compares and bit operations per recipe.  The built in recipes
+//! are compiled by `build.rs`.
+//!
+//! Actor ids are positions in the sorted item table and tag ids positions
+//! in the tag list.
+#[cfg(feature = "json")]
+use crate::{Item, Map, RecipeBase, Str};
+use std::borrow::Cow;
+
+/// Recipes from this index on only match a single kind of ingredient
+pub(crate) const STRICT: usize = 125;
+
+/// Id for a missing tag, never part of a recipe
+pub(crate) const NO_TAG: u16 = u16::MAX;
+
+/// Id for an actor or tag of a recipe that no ingredient has
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+pub(crate) const UNKNOWN: u16 = u16::MAX - 1;
+
+/// Ingredients per cook handled by the position masks
+const MAX_ITEMS: usize = 8;
+
+/// Bit used for an id in the quick rejection masks, may collide
+const fn bit(id: u16) -> u128 {
+    1 << (id & 127)
+}
+
+/// Alternatives, tried in order, with a mask of all of them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Group {
+    ids: Cow<'static, [u16]>,
+    mask: u128,
+}
+
+impl Group {
+    pub(crate) const fn from_static(ids: &'static [u16]) -> Self {
+        let mut mask = 0;
+        let mut i = 0;
+        while i < ids.len() {
+            mask |= bit(ids[i]);
+            i += 1;
+        }
+        Self {
+            ids: Cow::Borrowed(ids),
+            mask,
+        }
+    }
+    #[cfg(feature = "json")]
+    fn new(ids: Vec<u16>) -> Self {
+        let mask = ids.iter().fold(0, |m, id| m | bit(*id));
+        Self {
+            ids: ids.into(),
+            mask,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Compiled {
+    pub actors: Cow<'static, [Group]>,
+    pub tags: Cow<'static, [Group]>,
+    pub strict_actors: Option<Group>,
+    pub strict_tags: Option<Group>,
+}
+
+/// Ingredients to match, as interned ids by position
+struct Input {
+    n: usize,
+    actors: [u16; MAX_ITEMS],
+    tags: [u16; MAX_ITEMS],
+    present: u128,
+    present_tags: u128,
+}
+
+impl Input {
+    fn new(items: &[(u16, u16)]) -> Self {
+        assert!(items.len() <= MAX_ITEMS, "too many ingredients to match");
+        let mut out = Self {
+            n: items.len(),
+            actors: [0; MAX_ITEMS],
+            tags: [NO_TAG; MAX_ITEMS],
+            present: 0,
+            present_tags: 0,
+        };
+        for (i, (actor, tag)) in items.iter().enumerate() {
+            out.actors[i] = *actor;
+            out.tags[i] = *tag;
+            out.present |= bit(*actor);
+            if *tag != NO_TAG {
+                out.present_tags |= bit(*tag);
+            }
+        }
+        out
+    }
+    fn all(&self) -> u8 {
+        ((1u16 << self.n) - 1) as u8
+    }
+    fn positions(ids: &[u16], id: u16) -> u8 {
+        ids.iter()
+            .enumerate()
+            .filter(|(_, x)| **x == id)
+            .fold(0, |m, (i, _)| m | 1 << i)
+    }
+    fn actor_positions(&self, id: u16) -> u8 {
+        Self::positions(&self.actors[..self.n], id)
+    }
+    fn tag_positions(&self, id: u16) -> u8 {
+        Self::positions(&self.tags[..self.n], id)
+    }
+    /// Positions of the first alternative present in `left`
+    fn first_actor(&self, g: &Group, left: u8) -> u8 {
+        if g.mask & self.present == 0 {
+            return 0;
+        }
+        g.ids
+            .iter()
+            .map(|id| self.actor_positions(*id) & left)
+            .find(|m| *m != 0)
+            .unwrap_or(0)
+    }
+    fn first_tag(&self, g: &Group, left: u8) -> u8 {
+        if g.mask & self.present_tags == 0 {
+            return 0;
+        }
+        g.ids
+            .iter()
+            .map(|id| self.tag_positions(*id) & left)
+            .find(|m| *m != 0)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct Matcher {
+    recipes: Cow<'static, [Compiled]>,
+}
+
+impl Matcher {
+    pub(crate) const fn from_static(recipes: &'static [Compiled]) -> Self {
+        Self {
+            recipes: Cow::Borrowed(recipes),
+        }
+    }
+    /// Compile `recipes`, with ids from the positions in `items` and `tags`
+    #[cfg(feature = "json")]
+    pub fn new(items: &Map<Item>, tags: &[Str], recipes: &[RecipeBase]) -> Self {
+        let actor_id = |x: &Str| items.get(x).map_or(UNKNOWN, |item| item.id);
+        let tag_id = |x: &Str| {
+            tags.iter()
+                .position(|t| t == x)
+                .map_or(UNKNOWN, |i| i as u16)
+        };
+        let actors = |v: &[Str]| Group::new(v.iter().map(actor_id).collect());
+        let tags = |v: &[Str]| Group::new(v.iter().map(tag_id).collect());
+        let compile = |r: &RecipeBase| Compiled {
+            // A flat list of actors is tried once per entry in the loose pass
+            actors: (0..r.actors.len())
+                .map(|i| actors(r.actors.id(i)))
+                .collect(),
+            tags: r.tags.groups().into_iter().map(tags).collect(),
+            strict_actors: (r.actors.len() > 0).then(|| actors(r.actors.id(0))),
+            strict_tags: (r.tags.len() > 0).then(|| tags(r.tags.id(0))),
+        };
+        Self {
+            recipes: recipes.iter().map(compile).collect(),
+        }
+    }
+    fn matches_strict(&self, r: &Compiled, input: &Input) -> bool {
+        if input.n == 0
+            || input.actors[..input.n]
+                .iter()
+                .any(|x| *x != input.actors[0])
+        {
+            return false;
+        }
+        let mut left = input.all();
+        if let Some(g) = &r.strict_actors {
+            let hit = input.first_actor(g, left);
+            if hit == 0 {
+                return false;
+            }
+            left &= !hit;
+        }
+        if let Some(g) = &r.strict_tags {
+            let hit = input.first_tag(g, left);
+            if hit == 0 {
+                return false;
+            }
+            left &= !hit;
+        }
+        left == 0
+    }
+    fn matches_loose(&self, r: &Compiled, input: &Input) -> bool {
+        let mut left = input.all();
+        for g in r.actors.iter() {
+            let hit = input.first_actor(g, left);
+            if hit == 0 {
+                return false;
+            }
+            left &= !hit;
+        }
+        for g in r.tags.iter() {
+            let hit = input.first_tag(g, left);
+            if hit == 0 {
+                return false;
+            }
+            // Every copy of the ingredient holding the tag is used up
+            let k = hit.trailing_zeros() as usize;
+            left &= !input.actor_positions(input.actors[k]);
+        }
+        true
+    }
+    /// Index of the recipe matched by the (actor id, tag id) of each ingredient
+    pub fn find(&self, items: &[(u16, u16)]) -> Option<usize> {
+        let input = Input::new(items);
+        let strict = STRICT.min(self.recipes.len());
+        if let Some(i) = self.recipes[strict..]
+            .iter()
+            .position(|r| self.matches_strict(r, &input))
+        {
+            return Some(strict + i);
+        }
+        self.recipes[..strict]
+            .iter()
+            .position(|r| self.matches_loose(r, &input))
+    }
+}
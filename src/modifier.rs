@@ -1,7 +1,11 @@
+use crate::{tables, Effect};
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub enum Potency {
     #[default]
     Low = 1,
@@ -17,6 +21,16 @@ impl From<Potency> for i32 {
         }
     }
 }
+impl Potency {
+    /// Potency of an effect at `level`, 1 to 3
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            ..=1 => Potency::Low,
+            2 => Potency::Mid,
+            _ => Potency::High,
+        }
+    }
+}
 impl fmt::Display for Potency {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = match self {
@@ -28,8 +42,9 @@ impl fmt::Display for Potency {
     }
 }
 
-#[derive(Debug, Copy, Clone, Serialize, PartialEq, Eq, Hash, Default, Deserialize)]
-#[serde(try_from = "IntermediateData")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "json", serde(try_from = "IntermediateData"))]
 pub enum Modifier {
     AttackUp,
     DefenseUp,
@@ -48,40 +63,88 @@ pub enum Modifier {
 }
 
 impl Modifier {
+    /// Potency at which levels 2 and 3 start, 999 if never
+    ///
+    /// Read from the built in effect records.
+    #[deprecated(note = "use `Cook::tiers`, which follows `Cook::load_effects`")]
     pub fn threshold(&self) -> [i32; 2] {
-        match self {
-            Modifier::AttackUp => [5, 7],
-            Modifier::DefenseUp => [5, 7],
-            Modifier::ResistCold => [6, 999],
-            Modifier::ResistHot => [6, 999],
-            Modifier::ResistElectric => [4, 6],
-            Modifier::Fireproof => [7, 999],
-            Modifier::MovingSpeed => [5, 7],
-            Modifier::Quietness => [6, 9],
-            Modifier::LifeMaxUp => [999, 999],
-            Modifier::GutsRecover => [999, 999],
-            Modifier::ExGutsMaxUp => [999, 999],
-            Modifier::LifeRecover => [999, 999],
-            Modifier::None => [999, 999],
-        }
+        let tiers = match self {
+            Modifier::LifeMaxUp | Modifier::GutsRecover | Modifier::ExGutsMaxUp => vec![],
+            _ => tables::EFFECTS
+                .iter()
+                .find(|e| e.kind == *self)
+                .map(Effect::tiers)
+                .unwrap_or_default(),
+        };
+        [0, 1].map(|i| tiers.get(i).copied().unwrap_or(999))
     }
+    /// Name of the Elixir with this effect, empty if there is none
+    ///
+    /// Read from the built in modifier records.
+    #[deprecated(note = "use `Cook::elixir`, which follows `Cook::load_modifiers`")]
     pub fn elixir(&self) -> &str {
-        match self {
-            Modifier::AttackUp => "Mighty Elixir",
-            Modifier::DefenseUp => "Tough Elixir",
-            Modifier::ResistCold => "Spicy Elixir",
-            Modifier::ResistHot => "Chilly Elixir",
-            Modifier::ResistElectric => "Electro Elixir",
-            Modifier::Fireproof => "Fireproof Elixir",
-            Modifier::MovingSpeed => "Hasty Elixir",
-            Modifier::Quietness => "Sneaky Elixir",
-            Modifier::ExGutsMaxUp => "Enduring Elixir",
-            Modifier::GutsRecover => "Energizing Elixir",
-            Modifier::LifeMaxUp => "Hearty Elixir",
-            Modifier::LifeRecover => "",
-            Modifier::None => "",
+        tables::MODIFIERS
+            .iter()
+            .find(|m| m.kind == *self)
+            .map(|m| m.elixir.as_ref())
+            .unwrap_or_default()
+    }
+}
+
+/// Effect carried by some of the ingredients of a dish
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct EffectSource {
+    pub effect: Modifier,
+    /// Summed potency of the ingredients with this effect
+    pub potency: i32,
+    /// Positions of the ingredients with this effect
+    pub ingredients: Vec<usize>,
+}
+
+/// Effects of `items`, in the order they first appear
+pub(crate) fn effect_sources(items: &[&crate::Item]) -> Vec<EffectSource> {
+    let mut out: Vec<EffectSource> = vec![];
+    for (i, item) in items.iter().enumerate() {
+        if item.effect == Modifier::None {
+            continue;
+        }
+        match out.iter_mut().find(|s| s.effect == item.effect) {
+            Some(s) => {
+                s.potency += item.potency;
+                s.ingredients.push(i);
+            }
+            None => out.push(EffectSource {
+                effect: item.effect,
+                potency: item.potency,
+                ingredients: vec![i],
+            }),
         }
     }
+    out
+}
+
+/// Elixir name and dish prefix of an effect, from `cook_modifiers.json`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ModifierData {
+    #[cfg_attr(feature = "json", serde(rename = "type"))]
+    pub kind: Modifier,
+    /// Name of the Elixir with this effect
+    pub elixir: Cow<'static, str>,
+    /// Word put in front of the name of other dishes with this effect
+    #[cfg_attr(feature = "json", serde(default))]
+    pub prefix: Cow<'static, str>,
+}
+
+/// Entry read by [`crate::Cook::load_modifiers`], fields left out are kept
+#[cfg(feature = "json")]
+#[derive(Deserialize)]
+pub(crate) struct ModifierOverride {
+    #[serde(rename = "type")]
+    pub kind: Modifier,
+    pub elixir: Option<String>,
+    pub prefix: Option<String>,
 }
 
 impl fmt::Display for Modifier {
@@ -105,6 +168,7 @@ impl fmt::Display for Modifier {
     }
 }
 
+#[cfg(feature = "json")]
 #[derive(Deserialize, Debug, Default)]
 enum IntermediateData {
     AttackUp,
@@ -125,6 +189,7 @@ enum IntermediateData {
     Empty,
 }
 use std::convert::From;
+#[cfg(feature = "json")]
 impl From<IntermediateData> for Modifier {
     fn from(data: IntermediateData) -> Self {
         match data {
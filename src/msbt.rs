@@ -0,0 +1,274 @@
+//! Reader for MSBT message files, as found in the game's `Message` packs
+//!
+//! Only the label (`LBL1`) and text (`TXT2`) sections are read.  Control
+//! tags such as ruby or colour are removed from the text.  The `ActorType`
+//! files label display names `<actor>_Name`, which [`Msbt::names`] turns
+//! into a name table for [`Cook::add_names`].
+use crate::{Cook, CookError};
+use std::collections::HashMap;
+
+const MAGIC: &[u8] = b"MsgStdBn";
+const HEADER_SIZE: usize = 0x20;
+/// Start of a control tag: group, type, parameter size and parameters
+const TAG_OPEN: u16 = 0x0E;
+/// End of a control tag: group and type
+const TAG_CLOSE: u16 = 0x0F;
+
+/// Text of an MSBT file by label
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Msbt {
+    pub texts: HashMap<String, String>,
+}
+
+#[derive(Copy, Clone)]
+enum Encoding {
+    Utf8,
+    Utf16,
+}
+
+/// Bounds checked reads in the file's byte order
+struct Reader<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+fn invalid(msg: &str) -> CookError {
+    CookError::InvalidMsbt(msg.to_string())
+}
+
+impl<'a> Reader<'a> {
+    fn bytes(&self, at: usize, n: usize) -> Result<&'a [u8], CookError> {
+        at.checked_add(n)
+            .and_then(|end| self.data.get(at..end))
+            .ok_or_else(|| invalid("unexpected end of data"))
+    }
+    fn u16(&self, at: usize) -> Result<u16, CookError> {
+        let b = self.bytes(at, 2)?.try_into().unwrap();
+        Ok(match self.big_endian {
+            true => u16::from_be_bytes(b),
+            false => u16::from_le_bytes(b),
+        })
+    }
+    fn u32(&self, at: usize) -> Result<usize, CookError> {
+        let b = self.bytes(at, 4)?.try_into().unwrap();
+        Ok(match self.big_endian {
+            true => u32::from_be_bytes(b),
+            false => u32::from_le_bytes(b),
+        } as usize)
+    }
+}
+
+impl Msbt {
+    /// Parse the contents of an MSBT file
+    pub fn parse(data: &[u8]) -> Result<Self, CookError> {
+        if !data.starts_with(MAGIC) {
+            return Err(invalid("not an MSBT file"));
+        }
+        let big_endian = match data.get(8..10) {
+            Some([0xFE, 0xFF]) => true,
+            Some([0xFF, 0xFE]) => false,
+            _ => return Err(invalid("bad byte order mark")),
+        };
+        let r = Reader { data, big_endian };
+        let encoding = match r.bytes(0x0C, 1)?[0] {
+            0 => Encoding::Utf8,
+            1 => Encoding::Utf16,
+            n => return Err(CookError::InvalidMsbt(format!("unsupported encoding {n}"))),
+        };
+        let sections = r.u16(0x0E)?;
+        let mut labels = None;
+        let mut texts = None;
+        let mut at = HEADER_SIZE;
+        for _ in 0..sections {
+            let magic = r.bytes(at, 4)?;
+            let size = r.u32(at + 4)?;
+            let body = Reader {
+                data: r.bytes(at + 0x10, size)?,
+                big_endian,
+            };
+            match magic {
+                b"LBL1" => labels = Some(read_labels(&body)?),
+                b"TXT2" => texts = Some(read_texts(&body, encoding)?),
+                _ => {}
+            }
+            // Sections are padded to 16 bytes
+            at += 0x10 + size.next_multiple_of(16);
+        }
+        let labels = labels.ok_or_else(|| invalid("missing LBL1 section"))?;
+        let texts = texts.ok_or_else(|| invalid("missing TXT2 section"))?;
+        let texts = labels
+            .into_iter()
+            .map(|(label, i)| {
+                let text = texts.get(i).ok_or_else(|| invalid("label without text"))?;
+                Ok((label, text.to_string()))
+            })
+            .collect::<Result<_, CookError>>()?;
+        Ok(Self { texts })
+    }
+    /// Read and parse an MSBT file
+    pub fn read<R: std::io::Read>(mut reader: R) -> Result<Self, CookError> {
+        let mut data = vec![];
+        reader.read_to_end(&mut data)?;
+        Self::parse(&data)
+    }
+    /// Display names by actor, from the `<actor>_Name` labels
+    pub fn names(&self) -> HashMap<String, String> {
+        self.texts
+            .iter()
+            .filter_map(|(label, text)| {
+                let actor = label.strip_suffix("_Name")?;
+                Some((actor.to_string(), text.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Labels and the index of their text, stored in a hash table of groups
+fn read_labels(r: &Reader) -> Result<Vec<(String, usize)>, CookError> {
+    let mut out = vec![];
+    for group in 0..r.u32(0)? {
+        let count = r.u32(4 + group * 8)?;
+        let mut at = r.u32(8 + group * 8)?;
+        for _ in 0..count {
+            let len = r.bytes(at, 1)?[0] as usize;
+            let label = std::str::from_utf8(r.bytes(at + 1, len)?)
+                .map_err(|_| invalid("label is not UTF-8"))?;
+            out.push((label.to_string(), r.u32(at + 1 + len)?));
+            at += 1 + len + 4;
+        }
+    }
+    Ok(out)
+}
+
+/// Texts in order, each up to the next offset or the end of the section
+fn read_texts(r: &Reader, encoding: Encoding) -> Result<Vec<String>, CookError> {
+    let count = r.u32(0)?;
+    let offsets = (0..count)
+        .map(|i| r.u32(4 + i * 4))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut out = vec![];
+    for (i, start) in offsets.iter().enumerate() {
+        let end = offsets.get(i + 1).copied().unwrap_or(r.data.len());
+        let text = Reader {
+            data: r.bytes(*start, end.saturating_sub(*start))?,
+            big_endian: r.big_endian,
+        };
+        out.push(read_text(&text, encoding)?);
+    }
+    Ok(out)
+}
+
+/// Text up to the terminating null, with control tags removed
+fn read_text(r: &Reader, encoding: Encoding) -> Result<String, CookError> {
+    let width = match encoding {
+        Encoding::Utf8 => 1,
+        Encoding::Utf16 => 2,
+    };
+    let mut units = vec![];
+    let mut at = 0;
+    while at < r.data.len() {
+        let unit = match encoding {
+            Encoding::Utf8 => r.bytes(at, 1)?[0] as u16,
+            Encoding::Utf16 => r.u16(at)?,
+        };
+        at += width;
+        match unit {
+            0 => break,
+            TAG_OPEN => at += 6 + r.u16(at + 4)? as usize,
+            TAG_CLOSE => at += 4,
+            _ => units.push(unit),
+        }
+    }
+    match encoding {
+        Encoding::Utf8 => {
+            let bytes: Vec<u8> = units.into_iter().map(|x| x as u8).collect();
+            String::from_utf8(bytes).map_err(|_| invalid("text is not UTF-8"))
+        }
+        Encoding::Utf16 => String::from_utf16(&units).map_err(|_| invalid("text is not UTF-16")),
+    }
+}
+
+impl Cook {
+    /// Add the display names in the MSBT file `reader` to `language`
+    ///
+    /// Reads an `ActorType` message file, e.g. `ActorType/Item.msbt` from
+    /// `Msg_USen.product.ssarc`.
+    pub fn load_msbt<R: std::io::Read>(
+        &mut self,
+        language: &str,
+        reader: R,
+    ) -> Result<(), CookError> {
+        let msbt = Msbt::read(reader)?;
+        self.add_names(language, msbt.names());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn read(path: &str) -> Msbt {
+        Msbt::read(File::open(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let m = read("t/msbt/USen_Item.msbt");
+        assert_eq!(m.texts.len(), 6);
+        assert_eq!(m.texts["Item_Fruit_A_Name"], "Apple");
+        assert_eq!(m.texts["Item_Enemy_00_Name"], "Bokoblin Horn");
+        let names = m.names();
+        assert_eq!(names.len(), 4);
+        assert_eq!(names["Item_PlantGet_B"], "Hearty Radish");
+        assert_eq!(names["Item_Cook_B_02"], "Simmered Fruit");
+        // Big endian, with opening and closing tags
+        let names = read("t/msbt/EUde_Item.msbt").names();
+        assert_eq!(names["Item_Fruit_A"], "Apfel");
+        assert_eq!(names["Item_PlantGet_B"], "Energiewurzel");
+        assert_eq!(names["Item_Cook_B_02"], "Gedünstete Früchte");
+        // UTF-16 with a ruby tag, as in the Japanese files
+        let m = read("t/msbt/JPja_Item.msbt");
+        assert_eq!(m.names()["Item_Fruit_A"], "リンゴ");
+        assert_eq!(m.texts["Item_Fruit_A_Desc"], "ハイラルの木になる果物");
+        // UTF-8
+        let names = read("t/msbt/utf8_Item.msbt").names();
+        assert_eq!(names["Item_Fruit_A"], "リンゴ");
+
+        let data = std::fs::read("t/msbt/USen_Item.msbt").unwrap();
+        for n in [0, 8, 0x20, 0x40, data.len() - 20] {
+            assert!(matches!(
+                Msbt::parse(&data[..n]),
+                Err(CookError::InvalidMsbt(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn names_match_json() {
+        // Built in names agree with the English test file; the files in
+        // t/msbt are built by mkmsbt.py, not taken from the game
+        let c = Cook::new();
+        for (actor, name) in read("t/msbt/USen_Item.msbt").names() {
+            assert_eq!(
+                c.names.get(&actor).map(|x| x.as_ref()),
+                Some(name.as_str()),
+                "{actor}"
+            );
+        }
+    }
+
+    #[test]
+    fn load_msbt() {
+        let mut c = Cook::new();
+        let fp = File::open("t/msbt/EUde_Item.msbt").unwrap();
+        c.load_msbt("de", fp).unwrap();
+        c.set_language("de").unwrap();
+        let r = c.cook(&["Apfel", "Energiewurzel"]);
+        assert_eq!(
+            c.localized_names(&r.ingredients),
+            ["Apfel", "Energiewurzel"]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+//! The game's random number generator, `sead::Random`
+//!
+//! A xorshift128 generator seeded the same way as the game.  Bounded values
+//! scale the 32 bit output, `(u32 * max) >> 32`, rather than using a modulo.
+//! The test vectors come from an independent implementation of the same
+//! algorithm, not from a console.  Cooking with it follows the draw order
+//! modelled in `roll`, which is not known to be the game's, so it does not
+//! predict the game's crits.
+use crate::CookRng;
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Multiplier used to expand a seed into the four state words
+const SEED_MUL: u32 = 0x6C07_8965;
+
+/// `sead::Random`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct SeadRandom {
+    state: [u32; 4],
+}
+
+impl SeadRandom {
+    /// Generator seeded with `seed`, as `sead::Random::init(u32)`
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0; 4];
+        let mut prev = seed;
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = SEED_MUL
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(i as u32 + 1);
+            prev = *s;
+        }
+        Self { state }
+    }
+    /// Generator with the state words read from the game
+    pub fn from_state(state: [u32; 4]) -> Self {
+        Self { state }
+    }
+    pub fn state(&self) -> [u32; 4] {
+        self.state
+    }
+    /// Next 32 bit value, as `sead::Random::getU32()`
+    pub fn next_u32(&mut self) -> u32 {
+        let [x, y, z, w] = self.state;
+        let t = x ^ (x << 11);
+        let next = w ^ (w >> 19) ^ t ^ (t >> 8);
+        self.state = [y, z, w, next];
+        next
+    }
+}
+
+impl CookRng for SeadRandom {
+    /// Value in `0..max`, as `sead::Random::getU32(max)`
+    fn below(&mut self, max: u32) -> u32 {
+        ((self.next_u32() as u64 * max as u64) >> 32) as u32
+    }
+}
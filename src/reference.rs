@@ -0,0 +1,141 @@
+//! Recipe matching of the original string engine, kept to check against
+//!
+//! `Cook::find_recipe` used this before recipes were compiled for the
+//! matcher and `explain` was added.  It is carried over unchanged apart
+//! from the types and the verbose printing, so both of those can be tested
+//! against an engine they were not derived from.
+use crate::{AVec, Cook, RecipeBase, Str};
+
+fn inter(a: &[Str], b: &[String]) -> Vec<String> {
+    let mut c = vec![];
+    for ai in a {
+        if b.iter().any(|x| x == ai) {
+            c.push(ai.to_string())
+        }
+    }
+    c
+}
+
+fn matches(r: &RecipeBase, items: &[String], tags: &[String], strict: bool) -> bool {
+    if strict {
+        let mut v = items.to_vec();
+        v.sort_unstable();
+        v.dedup();
+        if v.len() != 1 {
+            return false;
+        }
+    }
+    let Some((items_t, tags_t)) = matches_actors(r, items.to_vec(), tags.to_vec(), strict) else {
+        return false;
+    };
+    let Some(items_t) = matches_tags(r, items_t, tags_t, strict) else {
+        return false;
+    };
+    if strict {
+        return items_t.is_empty();
+    }
+    true
+}
+
+fn matches_actors(
+    r: &RecipeBase,
+    mut items_t: Vec<String>,
+    mut tags_t: Vec<String>,
+    strict: bool,
+) -> Option<(Vec<String>, Vec<String>)> {
+    if strict {
+        if r.actors.len() == 0 {
+            return Some((items_t, tags_t));
+        }
+        let v = inter(r.actors.id(0), &items_t);
+        if v.is_empty() {
+            return None;
+        }
+        let v = &v[0];
+        let mut k = items_t.iter().position(|x| x == v);
+        while let Some(i) = k {
+            items_t.remove(i);
+            tags_t.remove(i);
+            k = items_t.iter().position(|x| x == v);
+        }
+        return Some((items_t, tags_t));
+    }
+    for i in 0..r.actors.len() {
+        let v = inter(r.actors.id(i), &items_t);
+        if v.is_empty() {
+            return None;
+        }
+        let mut k = items_t.iter().position(|x| x == &v[0]);
+        while let Some(i) = k {
+            items_t.remove(i);
+            tags_t.remove(i);
+            k = items_t.iter().position(|x| x == &v[0]);
+        }
+    }
+    Some((items_t, tags_t))
+}
+
+fn matches_tags(
+    r: &RecipeBase,
+    mut items_t: Vec<String>,
+    mut tags_t: Vec<String>,
+    strict: bool,
+) -> Option<Vec<String>> {
+    if strict {
+        if r.tags.len() == 0 {
+            return Some(items_t);
+        }
+        let v = inter(r.tags.id(0), &tags_t);
+        if v.is_empty() {
+            return None;
+        }
+        let mut k = tags_t.iter().position(|x| x == &v[0]);
+        while let Some(i) = k {
+            items_t.remove(i);
+            tags_t.remove(i);
+            k = tags_t.iter().position(|x| x == &v[0]);
+        }
+        return Some(items_t);
+    }
+    let tags = match &r.tags {
+        AVec::Two(v) => v,
+        AVec::One(v) if v.is_empty() => return Some(items_t),
+        AVec::One(_) => panic!(":( {:?}", r),
+    };
+    for group in tags.iter() {
+        let mut k = None;
+        for tag in group.iter() {
+            k = tags_t.iter().position(|x| x == tag);
+            if k.is_some() {
+                break;
+            }
+        }
+        let item = items_t[k?].clone();
+        while let Some(i) = k {
+            items_t.remove(i);
+            tags_t.remove(i);
+            k = items_t.iter().position(|x| x == &item);
+        }
+    }
+    Some(items_t)
+}
+
+/// Id of the recipe chosen for the ingredient actors `items`
+pub(crate) fn find_recipe(c: &Cook, items: &[String]) -> i32 {
+    let tags: Vec<String> = items
+        .iter()
+        .map(|x| c.data[x.as_str()].tags.to_string())
+        .collect();
+    let n = 125;
+    for recipe in c.recipes[n..].iter() {
+        if matches(recipe, items, &tags, true) {
+            return recipe.id;
+        }
+    }
+    for recipe in c.recipes[..n].iter() {
+        if matches(recipe, items, &tags, false) {
+            return recipe.id;
+        }
+    }
+    c.dubious.id
+}
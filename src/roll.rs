@@ -0,0 +1,252 @@
+//! A model of rolling a single concrete cook
+//!
+//! The model draws random numbers in this order:
+//!
+//! 1. With Monster Extract (and a dish other than Dubious or Rock-Hard
+//!    Food) no critical success can happen.  If the dish restores hearts,
+//!    one draw of 2 picks the maximum or the minimum; if its effect wears off,
+//!    one draw of 3 picks 01:00, 10:00 or 30:00.
+//! 2. Otherwise one draw of 100 below the crit rate is a critical success,
+//!    followed, if more than one bonus is possible, by one draw picking the
+//!    bonus from [`Cook::crit_bonuses`].
+//!
+//! The order is an assumption: it was not taken from the game's code nor
+//! checked against the console, and the game may draw numbers this model
+//! does not.  A [`Roll`] is therefore a plausible cook for simulations, not
+//! a prediction, even with [`crate::SeadRandom`] at the game's state.
+use crate::{Cook, CookContext, CookError, CritBonus, Duration, Hearts, Modifier, Recipe};
+#[cfg(feature = "json")]
+use serde::{Deserialize, Serialize};
+
+/// Source of the random numbers used when rolling a cook
+pub trait CookRng {
+    /// Uniform value in `0..max`
+    fn below(&mut self, max: u32) -> u32;
+}
+
+impl<R: rand::Rng + ?Sized> CookRng for R {
+    fn below(&mut self, max: u32) -> u32 {
+        self.gen_range(0..max)
+    }
+}
+
+/// Durations Monster Extract picks from
+pub const EXTRACT_TIMES: [Duration; 3] = [
+    Duration::from_mins(1),
+    Duration::from_mins(10),
+    Duration::from_mins(30),
+];
+
+/// Hearts restored after Monster Extract
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub enum ExtractHearts {
+    /// A quarter heart
+    Minimum,
+    /// Hearts plus a critical success worth (+3 hearts)
+    Maximum,
+}
+
+/// What Monster Extract did to a dish
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ExtractRoll {
+    /// `None` if the dish restores no hearts
+    pub hearts: Option<ExtractHearts>,
+    /// `None` if the dish has no duration
+    pub time: Option<Duration>,
+}
+
+/// One possible Monster Extract result
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct ExtractOutcome {
+    pub roll: ExtractRoll,
+    /// Chance of this result, 0 to 1
+    pub probability: f32,
+    /// Dish with the result applied
+    pub recipe: Recipe,
+}
+
+/// A single cook, with every random choice made
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
+pub struct Roll {
+    pub recipe: Recipe,
+    /// Bonus added if the cook was a critical success
+    pub crit: Option<CritBonus>,
+    /// Monster Extract result, if it was used
+    pub extract: Option<ExtractRoll>,
+}
+
+impl Cook {
+    /// Cook `items` once, drawing the random choices from `rng`
+    ///
+    /// The same `rng` state always gives the same [`Roll`], so simulations
+    /// can be reproduced from a seed.  The draws follow the model described
+    /// in this module, so the roll is not what the game would cook from
+    /// that state.
+    pub fn cook_with_rng<S, R>(&self, items: &[S], rng: &mut R) -> Result<Roll, CookError>
+    where
+        S: AsRef<str>,
+        R: CookRng + ?Sized,
+    {
+        self.roll(items, &CookContext::default(), rng)
+    }
+    /// Cook `items` once in the circumstances `ctx`, drawing from `rng`
+    ///
+    /// Follows the same model as [`Cook::cook_with_rng`].
+    pub fn roll<S, R>(&self, items: &[S], ctx: &CookContext, rng: &mut R) -> Result<Roll, CookError>
+    where
+        S: AsRef<str>,
+        R: CookRng + ?Sized,
+    {
+        let mut base = self.try_cook_with(items, ctx)?;
+        base.crit_outcomes.clear();
+        base.extract_outcomes.clear();
+        if base.monster_rng {
+            let extract = ExtractRoll {
+                hearts: (!base.hp.is_zero()).then(|| match rng.below(2) {
+                    0 => ExtractHearts::Maximum,
+                    _ => ExtractHearts::Minimum,
+                }),
+                time: self
+                    .has_duration(&base)
+                    .then(|| EXTRACT_TIMES[rng.below(3) as usize]),
+            };
+            return Ok(Roll {
+                recipe: self.apply_extract(&base, &extract),
+                crit: None,
+                extract: Some(extract),
+            });
+        }
+        if base.crit_rate <= 0 || rng.below(100) >= base.crit_rate as u32 {
+            return Ok(Roll {
+                recipe: base,
+                crit: None,
+                extract: None,
+            });
+        }
+        let bonuses = self.crit_bonuses(&base);
+        let bonus = match bonuses.len() {
+            0 => None,
+            1 => Some(bonuses[0]),
+            n => Some(bonuses[rng.below(n as u32) as usize]),
+        };
+        Ok(Roll {
+            recipe: bonus.map_or(base.clone(), |b| self.apply_crit(&base, b)),
+            crit: bonus,
+            extract: None,
+        })
+    }
+    /// Every Monster Extract result for `r`
+    ///
+    /// Hearts and duration are picked independently, so there are up to six
+    /// results.
+    pub fn extract_outcomes(&self, r: &Recipe) -> Vec<ExtractOutcome> {
+        let hearts = if !r.hp.is_zero() {
+            vec![Some(ExtractHearts::Maximum), Some(ExtractHearts::Minimum)]
+        } else {
+            vec![None]
+        };
+        let times: Vec<Option<Duration>> = if self.has_duration(r) {
+            EXTRACT_TIMES.iter().map(|x| Some(*x)).collect()
+        } else {
+            vec![None]
+        };
+        let p = 1.0 / (hearts.len() * times.len()) as f32;
+        let mut out = vec![];
+        for hearts in &hearts {
+            for time in &times {
+                let roll = ExtractRoll {
+                    hearts: *hearts,
+                    time: *time,
+                };
+                out.push(ExtractOutcome {
+                    roll,
+                    probability: p,
+                    recipe: self.apply_extract(r, &roll),
+                });
+            }
+        }
+        out
+    }
+    /// Dish has an effect that wears off
+    fn has_duration(&self, r: &Recipe) -> bool {
+        !r.time.is_zero() && self.try_get_effect(r.effect).is_ok_and(|e| e.base_time > 0)
+    }
+    /// `r` after Monster Extract made the choices in `extract`
+    pub fn apply_extract(&self, r: &Recipe, extract: &ExtractRoll) -> Recipe {
+        let mut out = r.clone();
+        out.crit_outcomes.clear();
+        out.extract_outcomes.clear();
+        if let Some(hearts) = extract.hearts {
+            let ssa = self
+                .try_get_effect(Modifier::LifeRecover)
+                .map(|e| e.ssa)
+                .unwrap_or(0);
+            let hp = match hearts {
+                ExtractHearts::Minimum => Hearts::from_quarters(1),
+                ExtractHearts::Maximum => (out.hp + Hearts::from_quarters(ssa)).capped(),
+            };
+            out.set_hp(hp);
+        }
+        if let Some(time) = extract.time {
+            out.time = time;
+        }
+        out
+    }
+}
+
+impl Recipe {
+    /// Every possible result of cooking with its probability
+    ///
+    /// Includes the dish as is whenever no random outcome happens.
+    pub fn outcomes(&self) -> Vec<(f32, &Recipe)> {
+        let mut out: Vec<(f32, &Recipe)> = self
+            .crit_outcomes
+            .iter()
+            .map(|x| (x.probability, &x.recipe))
+            .chain(
+                self.extract_outcomes
+                    .iter()
+                    .map(|x| (x.probability, &x.recipe)),
+            )
+            .collect();
+        let rest = 1.0 - out.iter().map(|x| x.0).sum::<f32>();
+        if rest > 1e-6 {
+            out.insert(0, (rest, self));
+        }
+        out
+    }
+    /// Fewest hearts restored over every outcome
+    pub fn min_hp(&self) -> Hearts {
+        self.outcomes()
+            .iter()
+            .map(|x| x.1.hp)
+            .min()
+            .unwrap_or_default()
+    }
+    /// Average quarter hearts restored, weighted by probability
+    pub fn expected_hp(&self) -> f32 {
+        self.outcomes()
+            .iter()
+            .map(|(p, r)| p * r.hp.quarters() as f32)
+            .sum()
+    }
+    /// Shortest duration over every outcome
+    pub fn min_time(&self) -> Duration {
+        self.outcomes()
+            .iter()
+            .map(|x| x.1.time)
+            .min()
+            .unwrap_or_default()
+    }
+    /// Average duration in seconds, weighted by probability
+    pub fn expected_time(&self) -> f32 {
+        self.outcomes()
+            .iter()
+            .map(|(p, r)| p * r.time.secs() as f32)
+            .sum()
+    }
+}
@@ -0,0 +1,11 @@
+//! Cooking data as static tables
+//!
+//! Generated at build time from the embedded JSON files by `build.rs`,
+//! together with the indexes over them: items and names sorted by actor,
+//! actors by display name, dish actors and the compiled recipe matcher.
+//! [`crate::Cook::try_new`] borrows them without parsing or copying.
+use crate::matcher::{Compiled, Group, Matcher, NO_TAG};
+use crate::{AVec, Effect, Item, Map, Modifier, ModifierData, RecipeBase, Str};
+use std::borrow::Cow;
+
+include!(concat!(env!("OUT_DIR"), "/tables.rs"));
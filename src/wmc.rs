@@ -1,5 +1,7 @@
+#[cfg(feature = "json")]
 use serde::{Deserialize, Serialize};
-#[derive(Debug, Copy, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "json", derive(Serialize, Deserialize))]
 pub struct WMC {
     pub attack_up: Option<i32>,
     pub durability_up: bool,
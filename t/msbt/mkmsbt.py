@@ -0,0 +1,76 @@
+"""Build the MSBT test files in this directory
+
+The files are synthetic: they follow the layout of the game's message
+files but are not taken from the game.  The texts are names as shown in
+game, and the control tags stand in for the colour and ruby tags the game
+uses.  Run from the repository root: python3 t/msbt/mkmsbt.py
+"""
+import struct, sys
+def tag(end, group, typ, params=b''):
+    return ('\x0e', struct.pack(end+'HHH', group, typ, len(params)) + params)
+def build(path, end, enc, entries):
+    bom = b'\xfe\xff' if end == '>' else b'\xff\xfe'
+    def text(parts):
+        out = b''
+        for p in parts:
+            if isinstance(p, tuple):
+                ch, rest = p
+                out += (ch.encode('utf-16' + ('-be' if end=='>' else '-le')) if enc==1 else ch.encode()) + rest
+            else:
+                out += p.encode('utf-16' + ('-be' if end=='>' else '-le')) if enc==1 else p.encode()
+        out += b'\0\0' if enc==1 else b'\0'
+        return out
+    # LBL1 with 3 slots
+    nslots = 3
+    slots = [[] for _ in range(nslots)]
+    for i,(label,_) in enumerate(entries):
+        h = 0
+        for c in label.encode(): h = (h*0x492 + c) & 0xffffffff
+        slots[h % nslots].append((label, i))
+    hdr = struct.pack(end+'I', nslots)
+    off = 4 + 8*nslots
+    body = b''
+    for s in slots:
+        hdr += struct.pack(end+'II', len(s), off + len(body))
+        for label, i in s:
+            body += bytes([len(label)]) + label.encode() + struct.pack(end+'I', i)
+    lbl = hdr + body
+    strs = [text(t) for _,t in entries]
+    txt = struct.pack(end+'I', len(strs))
+    off = 4 + 4*len(strs)
+    data = b''
+    for s in strs:
+        txt += struct.pack(end+'I', off + len(data)); data += s
+    txt += data
+    atr = struct.pack(end+'II', len(strs), 0)
+    def sec(magic, d):
+        out = magic + struct.pack(end+'I', len(d)) + b'\0'*8 + d
+        out += b'\xab' * (-len(out) % 16)
+        return out
+    secs = sec(b'LBL1', lbl) + sec(b'ATR1', atr) + sec(b'TXT2', txt)
+    size = 0x20 + len(secs)
+    head = b'MsgStdBn' + bom + b'\0\0' + bytes([enc, 3]) + struct.pack(end+'HHI', 3, 0, size) + b'\0'*10
+    open(path,'wb').write(head + secs)
+
+build('t/msbt/USen_Item.msbt', '<', 1, [
+    ('Item_Fruit_A_Name', ['Apple']),
+    ('Item_Fruit_A_Desc', ['A common fruit found on trees all around Hyrule.']),
+    ('Item_PlantGet_B_Name', ['Hearty Radish']),
+    ('Item_Cook_B_02_Name', ['Simmered Fruit']),
+    ('Item_Cook_B_02_PictureBook', ['Simmered Fruit']),
+    ('Item_Enemy_00_Name', ['Bokoblin ', tag('<', 0, 3, b'\x04\x00\xff\x00'), 'Horn']),
+])
+build('t/msbt/EUde_Item.msbt', '>', 1, [
+    ('Item_Fruit_A_Name', ['Apfel']),
+    ('Item_PlantGet_B_Name', [tag('>', 0, 3, b'\x00\x04\x00\xff'), 'Energie', ('\x0f', struct.pack('>HH', 0, 3)), 'wurzel']),
+    ('Item_Cook_B_02_Name', ['Gedünstete Früchte']),
+])
+# UTF-16 as in the game, with a ruby tag over a kanji
+build('t/msbt/JPja_Item.msbt', '<', 1, [
+    ('Item_Fruit_A_Name', ['リンゴ']),
+    ('Item_Fruit_A_Desc', ['ハイラルの', tag('<', 0, 0, struct.pack('<HH', 2, 2) + 'き'.encode('utf-16-le')), '木になる果物']),
+])
+# UTF-8, which the format allows but the game does not use
+build('t/msbt/utf8_Item.msbt', '<', 0, [
+    ('Item_Fruit_A_Name', ['リンゴ']),
+])